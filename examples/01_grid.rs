@@ -5,18 +5,18 @@ fn text_cell(s: &str) -> Box<Text> {
         text: s.to_string(),
         halign: HAlign::Centre,
         valign: VAlign::Centre,
+        ..Text::default()
     })
 }
 
 fn main() {
-    Rcui::exec(Proxy::wrap(
-        |origin, rcui, event| {
-            if let Event::KeyStroke(key) = event {
-                if *key as u8 as char == 'q' {
-                    rcui.quit();
-                }
+    Rcui::exec(Filter::wrap(
+        |_, rcui, event| match event {
+            _ if event.key() == Some(Key::Char('q')) => {
+                rcui.quit();
+                true
             }
-            origin.handle_event(rcui, event);
+            _ => false,
         },
         Column::new(vec![
             Cell::Fixed(3.0, text_cell("This is the Grid Example:")),
@@ -1,4 +1,3 @@
-use rcui::curses::*;
 use rcui::*;
 
 fn title(title: &str, widget: Box<dyn Widget>) -> Box<dyn Widget> {
@@ -9,33 +8,33 @@ fn title(title: &str, widget: Box<dyn Widget>) -> Box<dyn Widget> {
                 text: title.to_string(),
                 halign: HAlign::Centre,
                 valign: VAlign::Centre,
+                ..Text::default()
             }),
         ),
         Cell::One(widget),
     ]);
-    title.group.focus = 1;
+    title.group.set_focus(1);
     title
 }
 
 fn main() {
     Rcui::exec(title(
-        "jk to move up and down",
-        Proxy::wrap(
-            |list, context, event| {
-                if let Event::KeyStroke(key) = event {
-                    match *key {
-                        KEY_NPAGE => list.page_down(),
-                        KEY_PPAGE => list.page_up(),
-                        key => match key as u8 as char {
-                            'q' => context.quit(),
-                            'j' => list.down(),
-                            'k' => list.up(),
-                            _ => {}
-                        },
+        "jk to move up and down, gg and G to jump to the top and the bottom",
+        KeySeq::wrap(
+            Filter::new(
+                |_, context, event| match event.key() {
+                    Some(Key::Char('q')) => {
+                        context.quit();
+                        true
                     }
-                }
-            },
-            ItemList::new((0..100).map(|x| format!("item-{:02}", x)).collect()),
+                    _ => false,
+                },
+                ItemList::new((0..100).map(|x| format!("item-{:02}", x)).collect()),
+            ),
+            vec![
+                ("gg", |filter, _| filter.origin.home()),
+                ("G", |filter, _| filter.origin.end()),
+            ],
         ),
     ));
     println!("Quitting gracefully uwu");
@@ -1,25 +1,5 @@
-use rcui::curses::*;
 use rcui::*;
 
-fn item_list_controls<T: ToString + Clone>(item_list: ItemList<T>) -> Box<Proxy<ItemList<T>>> {
-    Proxy::wrap(
-        |list, _, event| {
-            if let Event::KeyStroke(key) = event {
-                match *key {
-                    KEY_NPAGE => list.page_down(),
-                    KEY_PPAGE => list.page_up(),
-                    key => match key as u8 as char {
-                        'j' => list.down(),
-                        'k' => list.up(),
-                        _ => {}
-                    },
-                }
-            }
-        },
-        item_list,
-    )
-}
-
 fn title(title: &str, widget: Box<dyn Widget>) -> Box<dyn Widget> {
     let mut title = Column::wrap(vec![
         Cell::Fixed(
@@ -28,11 +8,12 @@ fn title(title: &str, widget: Box<dyn Widget>) -> Box<dyn Widget> {
                 text: title.to_string(),
                 halign: HAlign::Centre,
                 valign: VAlign::Centre,
+                ..Text::default()
             }),
         ),
         Cell::One(widget),
     ]);
-    title.group.focus = 1;
+    title.group.set_focus(1);
     title
 }
 
@@ -42,19 +23,21 @@ fn main() {
     let right_list = ItemList::new((0..n).map(|x| format!("bar-{}", x)).collect());
     Rcui::exec(title(
         "jk to move up and down, TAB to switch the focus",
-        Proxy::wrap(
-            |hbox, context, event| {
-                if let Event::KeyStroke(key) = event {
-                    match *key as u8 as char {
-                        'q' => context.quit(),
-                        '\t' => hbox.focus_next(),
-                        _ => hbox.handle_event(context, event),
-                    }
+        Filter::wrap(
+            |hbox, context, event| match event.key() {
+                Some(Key::Char('q')) => {
+                    context.quit();
+                    true
+                }
+                Some(Key::Tab) => {
+                    hbox.focus_next();
+                    true
                 }
+                _ => false,
             },
             Row::new(vec![
-                Cell::One(item_list_controls(left_list)),
-                Cell::One(item_list_controls(right_list)),
+                Cell::One(Box::new(left_list)),
+                Cell::One(Box::new(right_list)),
             ]),
         ),
     ));
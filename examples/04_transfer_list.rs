@@ -1,33 +1,26 @@
-use rcui::curses::*;
 use rcui::*;
 
 struct AddItem {
     label: String,
 }
 
-fn item_list_controls(item_list: ItemList<String>) -> Box<Proxy<ItemList<String>>> {
-    Proxy::wrap(
+fn item_list_controls(item_list: ItemList<String>) -> Box<Filter<ItemList<String>>> {
+    Filter::wrap(
         |list, context, event| match event {
-            Event::KeyStroke(key) => match *key {
-                KEY_NPAGE => list.page_down(),
-                KEY_PPAGE => list.page_up(),
-                key => match key as u8 as char {
-                    'j' => list.down(),
-                    'k' => list.up(),
-                    '\n' => {
-                        if let Some(item) = list.remove() {
-                            context.push_event(Event::Custom(Box::new(AddItem { label: item })));
-                        }
-                    }
-                    _ => {}
-                },
+            Event::Custom(event) => match event.downcast_ref::<AddItem>() {
+                Some(add_item) => {
+                    list.push_sorted_by(add_item.label.clone(), |a, b| a.cmp(b));
+                    true
+                }
+                None => false,
             },
-            Event::Custom(event) => {
-                if let Some(add_item) = event.downcast_ref::<AddItem>() {
-                    list.push(add_item.label.clone());
+            _ if event.key() == Some(Key::Enter) => {
+                if let Some(item) = list.remove() {
+                    context.push_event(Event::Custom(Box::new(AddItem { label: item })));
                 }
+                true
             }
-            _ => {}
+            _ => false,
         },
         item_list,
     )
@@ -41,11 +34,12 @@ fn title(title: &str, widget: Box<dyn Widget>) -> Box<dyn Widget> {
                 text: title.to_string(),
                 halign: HAlign::Centre,
                 valign: VAlign::Centre,
+                ..Text::default()
             }),
         ),
         Cell::One(widget),
     ]);
-    title.group.focus = 1;
+    title.group.set_focus(1);
     title
 }
 
@@ -56,22 +50,27 @@ fn main() {
 
     Rcui::exec(title(
         "jk to move up and down, ENTER to transfer an element, TAB to switch the focus",
-        Proxy::wrap(
+        Filter::wrap(
             |row, context, event| match event {
-                Event::KeyStroke(key) => match *key as u8 as char {
-                    'q' => context.quit(),
-                    '\t' => row.focus_next(),
-                    _ => row.handle_event(context, event),
-                },
-
                 Event::Custom(_) => {
                     assert!(row.group.cells.len() == 2);
-                    row.group.cells[1 - row.group.focus]
+                    let other = 1 - row.group.focus_index();
+                    row.group.cells[other]
                         .get_widget_mut()
-                        .handle_event(context, event);
+                        .handle_event(context, event)
                 }
 
-                _ => {}
+                _ => match event.key() {
+                    Some(Key::Char('q')) => {
+                        context.quit();
+                        true
+                    }
+                    Some(Key::Tab) => {
+                        row.focus_next();
+                        true
+                    }
+                    _ => false,
+                },
             },
             Row::new(vec![
                 Cell::One(item_list_controls(left_list)),
@@ -0,0 +1,67 @@
+use rcui::*;
+use std::fmt;
+
+struct Track {
+    artist: &'static str,
+    title: &'static str,
+    seconds: u32,
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} - {} ({}:{:02})",
+            self.artist,
+            self.title,
+            self.seconds / 60,
+            self.seconds % 60
+        )
+    }
+}
+
+fn main() {
+    let tracks = vec![
+        Track {
+            artist: "Boards of Canada",
+            title: "Roygbiv",
+            seconds: 151,
+        },
+        Track {
+            artist: "Aphex Twin",
+            title: "Xtal",
+            seconds: 294,
+        },
+        Track {
+            artist: "Autechre",
+            title: "Bike",
+            seconds: 503,
+        },
+        Track {
+            artist: "Burial",
+            title: "Archangel",
+            seconds: 238,
+        },
+    ];
+
+    let mut list = ItemList::new(tracks);
+    list.multi_select = true;
+
+    Rcui::exec(Filter::wrap(
+        |list, context, event| {
+            match event.key() {
+                Some(Key::Char('q')) => context.quit(),
+                Some(Key::Char('J')) => list.move_selected_down(),
+                Some(Key::Char('K')) => list.move_selected_up(),
+                Some(Key::Char('a')) => list.select_all(),
+                Some(Key::Char('c')) => list.clear_selection(),
+                Some(Key::Char('d')) => {
+                    list.remove();
+                }
+                _ => return false,
+            }
+            true
+        },
+        list,
+    ));
+}
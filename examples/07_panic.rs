@@ -0,0 +1,12 @@
+use rcui::*;
+
+fn main() {
+    Rcui::exec(Proxy::wrap(
+        |_, context, event| match event.key() {
+            Some(Key::Char('q')) => context.quit(),
+            Some(Key::Char('p')) => panic!("The widget panicked on purpose"),
+            _ => {}
+        },
+        Text::new("p to panic, q to quit"),
+    ));
+}
@@ -0,0 +1,42 @@
+use rcui::*;
+
+fn confirmation(item: &str) -> Box<dyn Widget> {
+    Dialog::wrap(Filter::wrap(
+        |_, context, event| match event.key() {
+            Some(Key::Enter) | Some(Key::Char('q')) => {
+                context.pop_layer();
+                true
+            }
+            _ => false,
+        },
+        Text::markdown(&format!(
+            "# {}\nYou picked **{}**.\n\nPress *Enter* to close the dialog.",
+            item, item
+        )),
+    ))
+}
+
+fn main() {
+    let mut list = ItemList::new((0..20).map(|x| format!("item-{:02}", x)).collect());
+    list.on_activate = Some(|item: &String, context| {
+        context.push_layer_with_scrim(confirmation(item), Scrim::Dim)
+    });
+
+    let options = Options {
+        mouse: true,
+        ..Options::default()
+    };
+    Rcui::exec_with_options(
+        Filter::wrap(
+            |_, context, event| match event {
+                _ if event.key() == Some(Key::Char('q')) => {
+                    context.quit();
+                    true
+                }
+                _ => false,
+            },
+            list,
+        ),
+        options,
+    );
+}
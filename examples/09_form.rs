@@ -0,0 +1,39 @@
+use rcui::*;
+
+fn field(label: &str) -> Cell {
+    Cell::Fixed(2.0, Field::wrap(label))
+}
+
+fn main() {
+    let mut address = Field::new("Address:");
+    address.position = LabelPosition::Above;
+
+    let mut form = Column::new(vec![
+        Cell::Fixed(
+            2.0,
+            Text::wrap("TAB to switch between the fields, ENTER to quit"),
+        ),
+        field("First name:"),
+        field("Last name:"),
+        field("Email:"),
+        Cell::Fixed(3.0, Box::new(address)),
+    ]);
+    form.group.set_focus(1);
+    // Skipping the help text
+    form.group.tab_order = Some(vec![1, 2, 3, 4]);
+
+    Rcui::exec(Filter::wrap(
+        |form, context, event| match event.key() {
+            Some(Key::Tab) => {
+                form.focus_next();
+                true
+            }
+            Some(Key::Enter) => {
+                context.quit();
+                true
+            }
+            _ => false,
+        },
+        form,
+    ));
+}
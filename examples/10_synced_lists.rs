@@ -0,0 +1,59 @@
+use rcui::*;
+
+struct Scroll {
+    offset: usize,
+}
+
+struct FileSizes {
+    files: ItemList<String>,
+    sizes: ItemList<String>,
+}
+
+impl Widget for FileSizes {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        let half = (rect.w * 0.5).floor();
+        context.render_widget(&mut self.files, &Rect { w: half, ..*rect }, active);
+        context.render_widget(
+            &mut self.sizes,
+            &Rect {
+                x: rect.x + half,
+                w: rect.w - half,
+                ..*rect
+            },
+            false,
+        );
+    }
+
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        match event {
+            Event::Custom(message) => match message.downcast_ref::<Scroll>() {
+                Some(scroll) => {
+                    self.sizes.window.offset = scroll.offset;
+                    true
+                }
+                None => false,
+            },
+            _ if event.key() == Some(Key::Char('q')) => {
+                context.quit();
+                true
+            }
+            _ => {
+                let consumed = self.files.handle_event(context, event);
+                self.sizes.cursor = self.files.cursor;
+                consumed
+            }
+        }
+    }
+}
+
+fn main() {
+    let mut files = ItemList::new((0..100).map(|x| format!("file-{:02}.txt", x)).collect());
+    files.on_scroll =
+        Some(|offset, context| context.push_event(Event::Custom(Box::new(Scroll { offset }))));
+
+    let mut sizes = ItemList::new((0..100).map(|x| format!("{} B", x * 37 % 1000)).collect());
+    // Following the offset of the files instead of its own cursor
+    sizes.scroll_policy = ScrollPolicy::Free;
+
+    Rcui::exec(Box::new(FileSizes { files, sizes }));
+}
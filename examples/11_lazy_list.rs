@@ -0,0 +1,19 @@
+use rcui::*;
+
+fn main() {
+    // None of the items exist until they're scrolled into the view
+    let mut list = LazyList::new(10_000_000, |index| Some(format!("item-{:08}", index)));
+    list.scroll_policy = ScrollPolicy::CenterSelection;
+
+    Rcui::exec(Filter::wrap(
+        |_, context, event| {
+            if event.key() == Some(Key::Char('q')) {
+                context.quit();
+                true
+            } else {
+                false
+            }
+        },
+        list,
+    ));
+}
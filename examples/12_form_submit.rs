@@ -0,0 +1,66 @@
+use rcui::*;
+
+fn required(text: &str) -> Result<(), String> {
+    if text.trim().is_empty() {
+        Err("The field is required".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn email(text: &str) -> Result<(), String> {
+    match text.split_once('@') {
+        Some((user, domain)) if !user.is_empty() && domain.contains('.') => Ok(()),
+        _ => Err("The email should look like user@example.com".to_string()),
+    }
+}
+
+fn summary(values: &[String]) -> Box<dyn Widget> {
+    Dialog::wrap(Filter::wrap(
+        |_, context, event| match event.key() {
+            Some(Key::Enter) => {
+                context.quit();
+                true
+            }
+            _ => false,
+        },
+        Text::markdown(&format!(
+            "# Submitted\n**{} {}** <{}>\n\nPress *Enter* to quit.",
+            values[0], values[1], values[2]
+        )),
+    ))
+}
+
+fn main() {
+    let form = Form::new(vec![
+        Field::new("First name:").validator(required),
+        Field::new("Last name:").validator(required),
+        Field::new("Email:").validator(email),
+    ]);
+
+    let mut root = Column::new(vec![
+        Cell::Fixed(
+            2.0,
+            Text::wrap(
+                "TAB/Shift+TAB to switch between the fields, ENTER to submit, ESC to cancel",
+            ),
+        ),
+        Cell::One(Filter::wrap(
+            |_, context, event| match event {
+                Event::Custom(message) => {
+                    if let Some(submit) = message.downcast_ref::<FormSubmit>() {
+                        context.push_layer_with_scrim(summary(&submit.values), Scrim::Dim);
+                    } else if message.is::<FormCancel>() {
+                        context.quit();
+                    }
+                    true
+                }
+                _ => false,
+            },
+            form,
+        )),
+    ]);
+    root.group.set_focus(1);
+
+    Rcui::exec(Box::new(root));
+}
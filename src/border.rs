@@ -0,0 +1,73 @@
+use super::*;
+
+/// Draws a frame with the lines of `style::line_set()` around the widget.
+/// `padding` is the amount of empty cells between the frame and the widget.
+pub struct Border {
+    pub widget: Box<dyn Widget>,
+    pub padding: f32,
+}
+
+impl Border {
+    pub fn new(widget: Box<dyn Widget>) -> Self {
+        Self {
+            widget,
+            padding: 0.0,
+        }
+    }
+
+    pub fn wrap(widget: Box<dyn Widget>) -> Box<Self> {
+        Box::new(Self::new(widget))
+    }
+
+    fn inner_rect(&self, rect: &Rect) -> Rect {
+        let inset = 1.0 + self.padding;
+        Rect {
+            x: rect.x + inset,
+            y: rect.y + inset,
+            w: (rect.w - 2.0 * inset).max(0.0),
+            h: (rect.h - 2.0 * inset).max(0.0),
+        }
+    }
+}
+
+impl Widget for Border {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        let x = rect.x.floor() as i32;
+        let y = rect.y.floor() as i32;
+        let w = rect.w.floor() as i32;
+        let h = rect.h.floor() as i32;
+        if w < 2 || h < 2 {
+            return;
+        }
+
+        let lines = style::line_set();
+        let horizontal = lines.horizontal.repeat(w as usize - 2);
+        mv(y, x);
+        addstr(lines.top_left);
+        addstr(&horizontal);
+        addstr(lines.top_right);
+        for row in 1..h - 1 {
+            mv(y + row, x);
+            addstr(lines.vertical);
+            mv(y + row, x + w - 1);
+            addstr(lines.vertical);
+        }
+        mv(y + h - 1, x);
+        addstr(lines.bottom_left);
+        addstr(&horizontal);
+        addstr(lines.bottom_right);
+
+        let inner = self.inner_rect(rect);
+        context.render_widget(self.widget.as_mut(), &inner, active);
+    }
+
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        self.widget.handle_event(context, event)
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        let inset = 2.0 * (1.0 + self.padding);
+        let (w, h) = self.widget.measure(&self.inner_rect(available));
+        (w + inset, h + inset)
+    }
+}
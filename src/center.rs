@@ -0,0 +1,67 @@
+use super::*;
+
+/// Renders the widget in a rectangle of the given size (or the measured
+/// size of the widget) centred within the parent one and clears everything
+/// around it. When the free space can't be split evenly the extra cell goes
+/// to the right and to the bottom.
+pub struct Center {
+    pub widget: Box<dyn Widget>,
+    pub size: Option<(f32, f32)>,
+}
+
+impl Center {
+    pub fn new(w: f32, h: f32, widget: Box<dyn Widget>) -> Self {
+        Self {
+            widget,
+            size: Some((w, h)),
+        }
+    }
+
+    pub fn wrap(w: f32, h: f32, widget: Box<dyn Widget>) -> Box<Self> {
+        Box::new(Self::new(w, h, widget))
+    }
+
+    /// Centres the widget in a rectangle of its measured size
+    pub fn fit(widget: Box<dyn Widget>) -> Self {
+        Self { widget, size: None }
+    }
+
+    pub fn fit_wrap(widget: Box<dyn Widget>) -> Box<Self> {
+        Box::new(Self::fit(widget))
+    }
+}
+
+impl Widget for Center {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        if rect.is_empty() {
+            return;
+        }
+
+        clear_rect(context, rect);
+
+        let (w, h) = self.measure(rect);
+        let w = w.min(rect.w).floor();
+        let h = h.min(rect.h).floor();
+        context.render_widget(
+            self.widget.as_mut(),
+            &Rect {
+                x: rect.x + ((rect.w - w) * 0.5).floor(),
+                y: rect.y + ((rect.h - h) * 0.5).floor(),
+                w,
+                h,
+            },
+            active,
+        );
+    }
+
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        self.widget.handle_event(context, event)
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        match self.size {
+            Some(size) => size,
+            None => self.widget.measure(available),
+        }
+    }
+}
@@ -14,30 +14,50 @@ impl Column {
     pub fn wrap(widgets: Vec<Cell>) -> Box<Self> {
         Box::new(Self::new(widgets))
     }
+
+    pub fn focus_next(&mut self) {
+        self.group.focus_next();
+    }
+
+    pub fn focus_prev(&mut self) {
+        self.group.focus_prev();
+    }
 }
 
 impl Widget for Column {
     fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
-        let n = self.group.cells.len();
-        let cell_size = self.group.cell_size(rect.h);
-        let mut y = rect.y;
-        for i in 0..n {
-            let widget_size = self.group.cells[i].size(cell_size);
-            self.group.cells[i].get_widget_mut().render(
-                context,
+        self.group.sync_focus(context);
+        if rect.is_empty() {
+            return;
+        }
+
+        let layout = self.group.split(rect.y, rect.h);
+        for (i, (y, h)) in layout.into_iter().enumerate() {
+            context.render_widget(
+                self.group.cells[i].get_widget_mut().as_mut(),
                 &Rect {
                     x: rect.x,
                     y,
                     w: rect.w,
-                    h: widget_size,
+                    h,
                 },
                 active && i == self.group.focus,
             );
-            y += widget_size;
         }
     }
 
-    fn handle_event(&mut self, context: &mut Rcui, event: &Event) {
-        self.group.handle_event(context, event);
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        self.group.handle_event(context, event)
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        self.group
+            .cells
+            .iter()
+            .map(|cell| match cell {
+                Cell::Fixed(size, widget) => (widget.measure(available).0, *size),
+                _ => cell.get_widget().measure(available),
+            })
+            .fold((0.0, 0.0), |(w, h), (cw, ch)| (w.max(cw), h + ch))
     }
 }
@@ -0,0 +1,78 @@
+use super::*;
+
+/// Renders and routes the events to `then` while `cond` is true and to
+/// `otherwise` (if any) while it's false. The inactive branch keeps its
+/// state until it's switched back to.
+pub struct If {
+    pub cond: bool,
+    pub then: Box<dyn Widget>,
+    pub otherwise: Option<Box<dyn Widget>>,
+}
+
+impl If {
+    pub fn new(cond: bool, then: Box<dyn Widget>, otherwise: Option<Box<dyn Widget>>) -> Self {
+        Self {
+            cond,
+            then,
+            otherwise,
+        }
+    }
+
+    pub fn wrap(
+        cond: bool,
+        then: Box<dyn Widget>,
+        otherwise: Option<Box<dyn Widget>>,
+    ) -> Box<Self> {
+        Box::new(Self::new(cond, then, otherwise))
+    }
+
+    pub fn toggle(&mut self) {
+        self.cond = !self.cond;
+    }
+
+    #[allow(clippy::borrowed_box)]
+    pub fn branch(&self) -> Option<&Box<dyn Widget>> {
+        if self.cond {
+            Some(&self.then)
+        } else {
+            self.otherwise.as_ref()
+        }
+    }
+
+    pub fn branch_mut(&mut self) -> Option<&mut Box<dyn Widget>> {
+        if self.cond {
+            Some(&mut self.then)
+        } else {
+            self.otherwise.as_mut()
+        }
+    }
+}
+
+impl Widget for If {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        if let Some(branch) = self.branch_mut() {
+            context.render_widget(branch.as_mut(), rect, active);
+        }
+    }
+
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        if let Event::Resize = event {
+            let mut consumed = self.then.handle_event(context, event);
+            if let Some(otherwise) = self.otherwise.as_mut() {
+                consumed |= otherwise.handle_event(context, event);
+            }
+            consumed
+        } else if let Some(branch) = self.branch_mut() {
+            branch.handle_event(context, event)
+        } else {
+            false
+        }
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        match self.branch() {
+            Some(branch) => branch.measure(available),
+            None => (0.0, 0.0),
+        }
+    }
+}
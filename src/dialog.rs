@@ -0,0 +1,53 @@
+use super::*;
+
+/// A bordered box centred within the parent rectangle and sized to fit
+/// its widget, which makes it handy for the layers. When the widget does
+/// not fit, the box is clamped to the parent rectangle and the widget gets
+/// less space than it asked for. The widgets that scroll themselves, like
+/// the markdown `Text` or the lists, can still be scrolled through in full.
+pub struct Dialog {
+    pub border: Border,
+}
+
+impl Dialog {
+    pub fn new(widget: Box<dyn Widget>) -> Self {
+        Self {
+            border: Border {
+                widget,
+                padding: 1.0,
+            },
+        }
+    }
+
+    pub fn wrap(widget: Box<dyn Widget>) -> Box<Self> {
+        Box::new(Self::new(widget))
+    }
+}
+
+impl Widget for Dialog {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        if rect.is_empty() {
+            return;
+        }
+
+        let (w, h) = self.border.measure(rect);
+        let w = w.min(rect.w).floor();
+        let h = h.min(rect.h).floor();
+        let dialog_rect = Rect {
+            x: rect.x + ((rect.w - w) * 0.5).floor(),
+            y: rect.y + ((rect.h - h) * 0.5).floor(),
+            w,
+            h,
+        };
+        clear_rect(context, &dialog_rect);
+        context.render_widget(&mut self.border, &dialog_rect, active);
+    }
+
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        self.border.handle_event(context, event)
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        self.border.measure(available)
+    }
+}
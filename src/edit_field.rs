@@ -13,11 +13,14 @@ pub struct EditField {
     text: Vec<char>,
     buffer: Vec<u8>,
     cursor: Cursor,
+    rect: Rect,
+    inactive_pair: Option<i16>,
+    counter: bool,
+    counter_limit: Option<usize>,
 }
 
 // TODO(#46): EditField does not support multiple lines (newlines)
 // TODO(#47): EditField does not have a way to jump one word forward/backward
-// TODO(#48): Some sort of clipboard support for EditField
 
 impl EditField {
     pub fn new() -> Self {
@@ -28,6 +31,10 @@ impl EditField {
                 position: 0,
                 selection_offset: 0,
             },
+            rect: Rect::default(),
+            inactive_pair: None,
+            counter: false,
+            counter_limit: None,
         }
     }
 
@@ -35,6 +42,69 @@ impl EditField {
         Box::new(Self::new())
     }
 
+    /// The rectangle the field was rendered into during the last frame
+    pub fn last_rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    /// By default the cursor is hidden while the field is inactive. Setting
+    /// the pair keeps showing it in that pair instead.
+    pub fn set_inactive_pair(&mut self, pair: i16) {
+        self.inactive_pair = Some(pair);
+    }
+
+    /// Renders the amount of characters in the bottom right corner of the
+    /// field, as `len/limit` if the limit is given. The counter turns into
+    /// `style::WARNING_PAIR` once the text gets within a tenth of the limit.
+    pub fn show_counter(&mut self, limit: Option<usize>) {
+        self.counter = true;
+        self.counter_limit = limit;
+    }
+
+    pub fn hide_counter(&mut self) {
+        self.counter = false;
+    }
+
+    /// The amount of characters in the text
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// The amount of the whitespace separated words in the text
+    pub fn word_count(&self) -> usize {
+        self.text().split_whitespace().count()
+    }
+
+    pub fn text(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    /// Replaces the whole text putting the cursor at the end of it
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.chars().filter(|c| !c.is_control()).collect();
+        self.cursor.position = self.text.len();
+        self.unselect();
+    }
+
+    /// The selected part of the text, if anything is selected
+    pub fn selected_text(&self) -> Option<String> {
+        self.selection()
+            .and_then(|selection| self.text.get(selection))
+            .map(|text| text.iter().collect())
+    }
+
+    /// Puts the selection to the clipboard removing it from the text
+    pub fn cut_selection_to_clipboard(&mut self, rcui: &mut Rcui) {
+        self.put_selection_to_clipboard(rcui);
+        if let Some(selection) = self.selection() {
+            self.delete_selection(selection);
+        }
+    }
+
     pub fn put_selection_to_clipboard(&self, rcui: &mut Rcui) {
         if let Some(selection) = self.selection() {
             if let Some(text) = self.text.get(selection) {
@@ -43,7 +113,7 @@ impl EditField {
         }
     }
 
-    pub fn paste_from_clipboard(&mut self, rcui: &Rcui) {
+    pub fn paste_from_clipboard(&mut self, rcui: &mut Rcui) {
         self.insert_chars(rcui.get_clipboard())
     }
 
@@ -201,58 +271,126 @@ impl EditField {
     }
 }
 
+impl EditField {
+    fn render_counter(&self, context: &mut Rcui, rect: &Rect) {
+        let len = self.len();
+        let (counter, pair) = match self.counter_limit {
+            Some(limit) => {
+                let warning = len * 10 >= limit * 9;
+                let pair = if warning {
+                    style::WARNING_PAIR
+                } else {
+                    style::REGULAR_PAIR
+                };
+                (format!("{}/{}", len, limit), pair)
+            }
+            None => (len.to_string(), style::REGULAR_PAIR),
+        };
+
+        let n = counter.chars().count() as f32;
+        if n > rect.w.floor() || rect.h < 1.0 {
+            return;
+        }
+        let x = (rect.x + rect.w).floor() as i32 - n as i32;
+        let y = (rect.y + rect.h).floor() as i32 - 1;
+        context.with_style(pair, A_NORMAL(), |_| mvaddstr(y, x, &counter));
+    }
+}
+
 // TODO(#46): EditField does not support multiple lines (newlines)
 
 impl Widget for EditField {
-    fn render(&mut self, _context: &mut Rcui, rect: &Rect, active: bool) {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        self.rect = *rect;
+        if rect.is_empty() {
+            return;
+        }
+
         let x = rect.x.floor() as i32;
         let y = rect.y.floor() as i32;
         mv(y, x);
         // TODO(#35): EditField does not wrap during the rendering
         addstr(&self.text.iter().collect::<String>());
-        if active {
+        let cursor_pair = if active {
+            Some(style::CURSOR_PAIR)
+        } else {
+            self.inactive_pair
+        };
+        if let Some(cursor_pair) = cursor_pair {
             match self.selection() {
                 None => {
                     mv(y, x + self.cursor.position as i32);
-                    attron(COLOR_PAIR(style::CURSOR_PAIR));
-                    if self.cursor.position >= self.text.len() {
-                        addstr(" ");
-                    } else {
-                        addstr(&self.text[self.cursor.position].to_string());
-                    }
-                    attroff(COLOR_PAIR(style::CURSOR_PAIR));
+                    let c = self.text.get(self.cursor.position).unwrap_or(&' ');
+                    context.with_style(cursor_pair, A_NORMAL(), |_| addstr(&c.to_string()));
                 }
                 Some(selection) => {
-                    for position in selection {
-                        mv(y, x + position as i32);
-                        attron(COLOR_PAIR(style::SELECTION_PAIR));
-                        if position >= self.text.len() {
-                            addstr(" ");
-                        } else {
-                            addstr(&self.text[position].to_string());
+                    let selection_pair = if active {
+                        style::SELECTION_PAIR
+                    } else {
+                        cursor_pair
+                    };
+                    context.with_style(selection_pair, A_NORMAL(), |_| {
+                        for position in selection {
+                            mv(y, x + position as i32);
+                            let c = self.text.get(position).unwrap_or(&' ');
+                            addstr(&c.to_string());
                         }
-                        attroff(COLOR_PAIR(style::SELECTION_PAIR));
-                    }
+                    });
                 }
             }
         }
+
+        if self.counter {
+            self.render_counter(context, rect);
+        }
     }
 
-    fn handle_event(&mut self, _context: &mut Rcui, event: &Event) {
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        if let Event::Paste(text) = event {
+            self.insert_chars(&text.chars().collect::<Vec<_>>());
+            return true;
+        }
+
         // TODO(#37): move the utf8 buffer mechanism to the main event loop
         if let Event::KeyStroke(key) = event {
-            self.buffer.push(*key as u8);
-            match String::from_utf8(self.buffer.clone()) {
-                Ok(s) => {
-                    self.insert_chars(&s.chars().collect::<Vec<_>>());
-                    self.buffer.clear()
-                }
-                Err(_) => {
-                    if self.buffer.len() >= 4 {
-                        self.buffer.clear()
+            match *key {
+                KEY_LEFT => self.left(),
+                KEY_RIGHT => self.right(),
+                KEY_SLEFT => self.select_left(),
+                KEY_SRIGHT => self.select_right(),
+                KEY_DC => self.delete_front(),
+                KEY_BACKSPACE | 127 => self.delete_back(),
+                // Ctrl+C and Ctrl+V only get here in InputMode::Raw, the
+                // terminal takes them over in the other modes
+                3 => self.put_selection_to_clipboard(context),
+                22 => self.paste_from_clipboard(context),
+                24 => self.cut_selection_to_clipboard(context),
+                // The control keys and the rest of the KEY_* codes are left
+                // to the widgets above
+                key if !(32..=255).contains(&key) => return false,
+                key => {
+                    self.buffer.push(key as u8);
+                    match String::from_utf8(self.buffer.clone()) {
+                        Ok(s) => {
+                            self.insert_chars(&s.chars().collect::<Vec<_>>());
+                            self.buffer.clear()
+                        }
+                        Err(_) => {
+                            if self.buffer.len() >= 4 {
+                                self.buffer.clear()
+                            }
+                        }
                     }
                 }
             }
+            return true;
         }
+
+        false
+    }
+
+    fn measure(&self, _available: &Rect) -> (f32, f32) {
+        // One extra cell for the cursor at the end of the text
+        ((self.text.len() + 1) as f32, 1.0)
     }
 }
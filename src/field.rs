@@ -0,0 +1,124 @@
+use super::*;
+
+#[derive(Clone, Copy)]
+pub enum LabelPosition {
+    Left,
+    Above,
+}
+
+/// Checks the text of a field returning the message to show for the
+/// invalid one
+pub type Validator = fn(&str) -> Result<(), String>;
+
+/// An `EditField` with a label. The label is rendered bold while the field
+/// is active and in the warning color while the field has an error.
+pub struct Field {
+    pub label: Text,
+    pub edit: EditField,
+    pub position: LabelPosition,
+    pub validator: Option<Validator>,
+    /// The message of the last failed validation
+    pub error: Option<String>,
+}
+
+impl Field {
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: Text::new(label),
+            edit: EditField::new(),
+            position: LabelPosition::Left,
+            validator: None,
+            error: None,
+        }
+    }
+
+    pub fn wrap(label: &str) -> Box<Self> {
+        Box::new(Self::new(label))
+    }
+
+    pub fn text(&self) -> String {
+        self.edit.text()
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.edit.set_text(text)
+    }
+
+    pub fn validator(mut self, validator: Validator) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    /// Runs the validator on the text remembering the error. A field
+    /// without a validator is always valid.
+    pub fn validate(&mut self) -> bool {
+        self.error = match self.validator {
+            Some(validator) => validator(&self.text()).err(),
+            None => None,
+        };
+        self.error.is_none()
+    }
+
+    fn label_width(&self) -> f32 {
+        // One extra cell to separate the label from the field
+        self.label.text.chars().count() as f32 + 1.0
+    }
+}
+
+impl Widget for Field {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        if rect.is_empty() {
+            return;
+        }
+
+        let (label_rect, edit_rect) = match self.position {
+            LabelPosition::Left => {
+                let label_w = self.label_width().min(rect.w);
+                (
+                    Rect {
+                        w: label_w,
+                        h: 1.0,
+                        ..*rect
+                    },
+                    Rect {
+                        x: rect.x + label_w,
+                        w: rect.w - label_w,
+                        ..*rect
+                    },
+                )
+            }
+            LabelPosition::Above => (
+                Rect { h: 1.0, ..*rect },
+                Rect {
+                    y: rect.y + 1.0,
+                    h: (rect.h - 1.0).max(0.0),
+                    ..*rect
+                },
+            ),
+        };
+
+        let label_attrs = if active { A_BOLD() } else { A_NORMAL() };
+        let label_pair = if self.error.is_some() {
+            style::WARNING_PAIR
+        } else {
+            0
+        };
+        let label = &mut self.label;
+        context.with_style(label_pair, label_attrs, |context| {
+            context.render_widget(label, &label_rect, active)
+        });
+        context.render_widget(&mut self.edit, &edit_rect, active);
+    }
+
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        self.edit.handle_event(context, event)
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        let (edit_w, _) = self.edit.measure(available);
+        match self.position {
+            LabelPosition::Left => (self.label_width() + edit_w, 1.0),
+            LabelPosition::Above => (self.label_width().max(edit_w), 2.0),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use super::*;
+
+/// Lets the filter look at the events before the origin does. The events
+/// the filter returns `true` for are considered consumed and never reach
+/// the origin.
+pub struct Filter<T> {
+    pub origin: T,
+    pub filter: fn(&mut T, &mut Rcui, &Event) -> bool,
+}
+
+impl<T: Widget> Filter<T> {
+    pub fn new(filter: fn(&mut T, &mut Rcui, &Event) -> bool, origin: T) -> Self {
+        Self { origin, filter }
+    }
+
+    pub fn wrap(filter: fn(&mut T, &mut Rcui, &Event) -> bool, origin: T) -> Box<Self> {
+        Box::new(Self::new(filter, origin))
+    }
+}
+
+impl<T: Widget> Widget for Filter<T> {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        context.render_widget(&mut self.origin, rect, active);
+    }
+
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        (self.filter)(&mut self.origin, context, event) || self.origin.handle_event(context, event)
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        self.origin.measure(available)
+    }
+}
@@ -0,0 +1,209 @@
+use super::*;
+
+/// Pushed as `Event::Custom` when the form is submitted with all the fields
+/// valid. The values follow the order of the fields.
+pub struct FormSubmit {
+    pub values: Vec<String>,
+}
+
+/// Pushed as `Event::Custom` when the form is cancelled with Esc
+pub struct FormCancel;
+
+/// The fields stacked from the top with Tab and Shift+Tab moving the focus
+/// between them like they do in `Group`. Enter validates all the fields and
+/// pushes `FormSubmit` when they're valid, otherwise the first invalid field
+/// gets the focus and its error is shown below the fields.
+pub struct Form {
+    pub fields: Vec<Field>,
+    pub focus: usize,
+    /// The indices of the fields Tab and Shift+Tab go through, see
+    /// `Group::tab_order`
+    pub tab_order: Option<Vec<usize>>,
+}
+
+impl Form {
+    pub fn new(fields: Vec<Field>) -> Self {
+        Self {
+            fields,
+            focus: 0,
+            tab_order: None,
+        }
+    }
+
+    pub fn wrap(fields: Vec<Field>) -> Box<Self> {
+        Box::new(Self::new(fields))
+    }
+
+    /// Clamps the index to the last field
+    pub fn set_focus(&mut self, index: usize) {
+        self.focus = index.min(self.fields.len().saturating_sub(1));
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focus = next_focus(self.focus, self.fields.len(), self.tab_order.as_deref());
+    }
+
+    pub fn focus_prev(&mut self) {
+        self.focus = prev_focus(self.focus, self.fields.len(), self.tab_order.as_deref());
+    }
+
+    pub fn values(&self) -> Vec<String> {
+        self.fields.iter().map(|field| field.text()).collect()
+    }
+
+    /// Validates every field, so all of them get their errors updated, and
+    /// focuses the first invalid one. Returns whether all of them are valid.
+    pub fn validate(&mut self) -> bool {
+        let mut first_invalid = None;
+        for (index, field) in self.fields.iter_mut().enumerate() {
+            if !field.validate() && first_invalid.is_none() {
+                first_invalid = Some(index);
+            }
+        }
+        if let Some(index) = first_invalid {
+            self.focus = index;
+        }
+        first_invalid.is_none()
+    }
+
+    pub fn submit(&mut self, context: &mut Rcui) {
+        if self.validate() {
+            context.push_event(Event::Custom(Box::new(FormSubmit {
+                values: self.values(),
+            })));
+        }
+    }
+
+    fn error(&self) -> Option<&String> {
+        self.fields
+            .get(self.focus)
+            .and_then(|field| field.error.as_ref())
+    }
+}
+
+impl Widget for Form {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        if rect.is_empty() {
+            return;
+        }
+
+        let bottom = rect.y + rect.h;
+        let mut y = rect.y;
+        for (index, field) in self.fields.iter_mut().enumerate() {
+            let (_, h) = field.measure(rect);
+            let h = h.min(bottom - y).floor();
+            let field_rect = Rect { y, h, ..*rect };
+            if field_rect.is_empty() {
+                return;
+            }
+            context.render_widget(field, &field_rect, active && index == self.focus);
+            y += h;
+        }
+
+        if let Some(error) = self.error() {
+            if y < bottom {
+                let line: String = error.chars().take(rect.w.floor() as usize).collect();
+                let x = rect.x.floor() as i32;
+                let y = y.floor() as i32;
+                context.with_style(style::WARNING_PAIR, A_NORMAL(), |_| mvaddstr(y, x, &line));
+            }
+        }
+    }
+
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        if let Event::Resize = event {
+            let mut consumed = false;
+            for field in self.fields.iter_mut() {
+                consumed |= field.handle_event(context, event);
+            }
+            return consumed;
+        }
+
+        match event.key() {
+            Some(Key::Tab) => self.focus_next(),
+            Some(Key::BackTab) => self.focus_prev(),
+            Some(Key::Enter) => self.submit(context),
+            Some(Key::Esc) => context.push_event(Event::Custom(Box::new(FormCancel))),
+            _ => {
+                return match self.fields.get_mut(self.focus) {
+                    Some(field) => field.handle_event(context, event),
+                    None => false,
+                }
+            }
+        }
+        true
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        let (w, h) = self
+            .fields
+            .iter()
+            .map(|field| field.measure(available))
+            .fold((0.0f32, 0.0), |(w, h), (fw, fh)| (w.max(fw), h + fh));
+        // A line for the error message
+        (w, h + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn required(text: &str) -> Result<(), String> {
+        if text.is_empty() {
+            Err("required".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn form() -> Form {
+        Form::new(vec![
+            Field::new("a:").validator(required),
+            Field::new("b:"),
+            Field::new("c:").validator(required),
+        ])
+    }
+
+    #[test]
+    fn submit_focuses_first_invalid_field() {
+        let mut context = Rcui::new();
+        let mut form = form();
+        form.fields[0].set_text("x");
+        form.set_focus(1);
+        form.submit(&mut context);
+        assert_eq!(form.focus, 2);
+        assert_eq!(form.error().map(String::as_str), Some("required"));
+        assert!(context.event_queue.is_empty());
+    }
+
+    #[test]
+    fn submit_pushes_values() {
+        let mut context = Rcui::new();
+        let mut form = form();
+        form.fields[0].set_text("x");
+        form.fields[2].set_text("z");
+        form.submit(&mut context);
+        match context.event_queue.pop_front() {
+            Some(Event::Custom(message)) => {
+                let submit = message.downcast_ref::<FormSubmit>().unwrap();
+                assert_eq!(submit.values, ["x", "", "z"]);
+            }
+            _ => panic!("FormSubmit was not pushed"),
+        }
+    }
+
+    #[test]
+    fn focus_follows_tab_order() {
+        let mut form = form();
+        form.tab_order = Some(vec![2, 0]);
+        form.focus_next();
+        assert_eq!(form.focus, 2);
+        form.focus_next();
+        assert_eq!(form.focus, 0);
+        form.focus_prev();
+        assert_eq!(form.focus, 2);
+        form.set_focus(10);
+        assert_eq!(form.focus, 2);
+    }
+}
@@ -36,31 +36,159 @@ impl Cell {
 pub struct Group {
     pub cells: Vec<Cell>,
     pub focus: usize,
+    /// The indices of the cells `focus_next` and `focus_prev` go through
+    /// instead of all of the cells in order. The cells that are not in it
+    /// can't be reached with them, and the out of range indices are skipped.
+    /// `insert` and `remove` keep it pointing at the same cells, but the
+    /// inserted cells are not added to it.
+    pub tab_order: Option<Vec<usize>>,
+    /// The cell that was last sent `Event::Focus`
+    notified: Option<usize>,
 }
 
 impl Group {
     pub fn new(cells: Vec<Cell>) -> Self {
-        Self { cells, focus: 0 }
+        Self {
+            cells,
+            focus: 0,
+            tab_order: None,
+            notified: None,
+        }
+    }
+
+    /// Focuses the cell like `set_focus` does
+    pub fn with_initial_focus(mut self, index: usize) -> Self {
+        self.set_focus(index);
+        self
     }
 
     pub fn wrap(cells: Vec<Cell>) -> Box<Self> {
         Box::new(Self::new(cells))
     }
 
+    pub fn focus_index(&self) -> usize {
+        self.focus
+    }
+
+    pub fn focused(&self) -> Option<&dyn Widget> {
+        self.cells
+            .get(self.focus)
+            .map(|cell| cell.get_widget().as_ref())
+    }
+
+    pub fn focused_mut(&mut self) -> Option<&mut Box<dyn Widget>> {
+        self.cells.get_mut(self.focus).map(Cell::get_widget_mut)
+    }
+
+    /// Clamps the index to the last cell
+    pub fn set_focus(&mut self, index: usize) {
+        self.focus = index.min(self.cells.len().saturating_sub(1));
+    }
+
     pub fn focus_next(&mut self) {
-        if !self.cells.is_empty() {
-            self.focus = (self.focus + 1) % self.cells.len()
-        }
+        self.focus = next_focus(self.focus, self.cells.len(), self.tab_order.as_deref());
     }
 
     pub fn focus_prev(&mut self) {
-        if !self.cells.is_empty() {
-            if self.focus == 0 {
-                self.focus = self.cells.len() - 1;
-            } else {
-                self.focus -= 1;
+        self.focus = prev_focus(self.focus, self.cells.len(), self.tab_order.as_deref());
+    }
+
+    /// Inserts the cell at the index (clamped to the amount of cells)
+    /// keeping the focus on the same cell
+    pub fn insert(&mut self, index: usize, cell: Cell) {
+        let index = index.min(self.cells.len());
+        if !self.cells.is_empty() && index <= self.focus {
+            self.focus += 1;
+        }
+        if let Some(order) = self.tab_order.as_mut() {
+            for i in order.iter_mut().filter(|i| **i >= index) {
+                *i += 1;
+            }
+        }
+        if let Some(notified) = self.notified.as_mut().filter(|i| **i >= index) {
+            *notified += 1;
+        }
+        self.cells.insert(index, cell);
+    }
+
+    /// Removes the cell keeping the focus on the same cell. If the focused
+    /// cell is removed the focus moves to the next one in `tab_order`, or
+    /// without it to the next one or to the last one.
+    pub fn remove(&mut self, index: usize) -> Option<Cell> {
+        if index >= self.cells.len() {
+            return None;
+        }
+
+        let cell = self.cells.remove(index);
+        let mut order_position = None;
+        if let Some(order) = self.tab_order.as_mut() {
+            order_position = order.iter().position(|i| *i == index);
+            order.retain(|i| *i != index);
+            for i in order.iter_mut().filter(|i| **i > index) {
+                *i -= 1;
+            }
+        }
+        self.notified = match self.notified {
+            Some(i) if i == index => None,
+            Some(i) if i > index => Some(i - 1),
+            notified => notified,
+        };
+        if index < self.focus {
+            self.focus -= 1;
+        } else if index == self.focus {
+            if let Some(next) = self.next_in_order(index, order_position) {
+                self.focus = next;
             }
         }
+        if self.focus >= self.cells.len() {
+            self.focus = self.cells.len().saturating_sub(1);
+        }
+        Some(cell)
+    }
+
+    /// The cell of `tab_order` that follows the removed `index`, which was
+    /// at `position` in the order
+    fn next_in_order(&self, index: usize, position: Option<usize>) -> Option<usize> {
+        let len = self.cells.len();
+        let order = self.tab_order.as_ref()?;
+        match position {
+            Some(position) => order[position..]
+                .iter()
+                .chain(order[..position].iter())
+                .find(|i| **i < len)
+                .copied(),
+            None => order
+                .iter()
+                .copied()
+                .filter(|i| *i < len)
+                .min_by_key(|i| (*i < index, *i)),
+        }
+    }
+
+    /// A replaced focused cell is sent `Event::Focus` anew
+    pub fn replace(&mut self, index: usize, cell: Cell) -> Option<Cell> {
+        if self.notified == Some(index) {
+            self.notified = None;
+        }
+        self.cells
+            .get_mut(index)
+            .map(|old| std::mem::replace(old, cell))
+    }
+
+    /// Sends `Event::Blur` to the cell that had the focus and `Event::Focus`
+    /// to the focused one if the focus has moved since the last time. The
+    /// containers call it before rendering and handling the events.
+    pub fn sync_focus(&mut self, context: &mut Rcui) {
+        if self.cells.is_empty() || self.notified == Some(self.focus) {
+            return;
+        }
+        if let Some(cell) = self.notified.and_then(|i| self.cells.get_mut(i)) {
+            cell.get_widget_mut().handle_event(context, &Event::Blur);
+        }
+        self.notified = Some(self.focus);
+        if let Some(cell) = self.cells.get_mut(self.focus) {
+            cell.get_widget_mut().handle_event(context, &Event::Focus);
+        }
     }
 
     pub fn cell_size(&self, mut size: f32) -> f32 {
@@ -76,12 +204,331 @@ impl Group {
 
         size / count as f32
     }
+
+    /// Splits `size` cells starting at `start` between the cells returning
+    /// the start and the size of each one. The fixed cells get their size
+    /// floored (and cut if they don't fit) and the flexible ones share the
+    /// rest in whole cells. The cells that don't divide evenly go one by one
+    /// to the first flexible cells, so they never differ by more than one
+    /// cell.
+    pub fn split(&self, start: f32, size: f32) -> Vec<(f32, f32)> {
+        let mut fixed = 0.0;
+        let mut units = 0;
+        for cell in self.cells.iter() {
+            match cell {
+                Cell::One(_) => units += 1,
+                Cell::Many(n, _) => units += n,
+                Cell::Fixed(s, _) => fixed += s.max(0.0).floor(),
+            }
+        }
+
+        let space = (size.max(0.0).floor() - fixed).max(0.0) as usize;
+        let base = space.checked_div(units).unwrap_or(0);
+        let mut remainder = space.checked_rem(units).unwrap_or(0);
+
+        let mut position = start.floor();
+        let end = position + size.max(0.0).floor();
+        let mut result = Vec::with_capacity(self.cells.len());
+        for cell in self.cells.iter() {
+            let n = match cell {
+                Cell::One(_) => 1,
+                Cell::Many(n, _) => *n,
+                Cell::Fixed(s, _) => {
+                    // The fixed cells that don't fit are cut
+                    let size = s.max(0.0).floor().min(end - position);
+                    result.push((position, size));
+                    position += size;
+                    continue;
+                }
+            };
+            let extra = remainder.min(n);
+            remainder -= extra;
+            let size = (base * n + extra) as f32;
+            result.push((position, size));
+            position += size;
+        }
+        result
+    }
+}
+
+/// The focus after `focus` among `len` children going through the valid
+/// indices of `tab_order` the way `Group::focus_next` does
+pub(crate) fn next_focus(focus: usize, len: usize, tab_order: Option<&[usize]>) -> usize {
+    if let Some(order) = valid_tab_order(len, tab_order) {
+        match order.iter().position(|i| *i == focus) {
+            Some(position) => order[(position + 1) % order.len()],
+            None => order[0],
+        }
+    } else if len > 0 {
+        (focus + 1) % len
+    } else {
+        focus
+    }
+}
+
+/// The focus before `focus`, see `next_focus`
+pub(crate) fn prev_focus(focus: usize, len: usize, tab_order: Option<&[usize]>) -> usize {
+    if let Some(order) = valid_tab_order(len, tab_order) {
+        match order.iter().position(|i| *i == focus) {
+            Some(0) | None => order[order.len() - 1],
+            Some(position) => order[position - 1],
+        }
+    } else if len > 0 {
+        (focus + len - 1) % len
+    } else {
+        focus
+    }
+}
+
+fn valid_tab_order(len: usize, tab_order: Option<&[usize]>) -> Option<Vec<usize>> {
+    let order: Vec<usize> = tab_order?.iter().copied().filter(|i| *i < len).collect();
+    if order.is_empty() {
+        None
+    } else {
+        Some(order)
+    }
 }
 
 impl Widget for Group {
-    fn handle_event(&mut self, context: &mut Rcui, event: &Event) {
-        if let Some(cell) = self.cells.get_mut(self.focus) {
-            cell.get_widget_mut().handle_event(context, event);
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        if let Event::Focus | Event::Blur = event {
+            // The focused cell learns about it from the event itself
+            self.notified = Some(self.focus).filter(|_| !self.cells.is_empty());
+        } else {
+            self.sync_focus(context);
+        }
+
+        if let Event::Resize = event {
+            let mut consumed = false;
+            for cell in self.cells.iter_mut() {
+                consumed |= cell.get_widget_mut().handle_event(context, event);
+            }
+            consumed
+        } else if let Some(cell) = self.cells.get_mut(self.focus) {
+            cell.get_widget_mut().handle_event(context, event)
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Log = Rc<RefCell<Vec<(usize, &'static str)>>>;
+
+    struct Focusable {
+        id: usize,
+        log: Log,
+    }
+
+    impl Widget for Focusable {
+        fn handle_event(&mut self, _context: &mut Rcui, event: &Event) -> bool {
+            let name = match event {
+                Event::Focus => "focus",
+                Event::Blur => "blur",
+                _ => return false,
+            };
+            self.log.borrow_mut().push((self.id, name));
+            true
         }
     }
+
+    fn focusable(n: usize, log: &Log) -> Group {
+        Group::new(
+            (0..n)
+                .map(|id| {
+                    Cell::One(Box::new(Focusable {
+                        id,
+                        log: log.clone(),
+                    }) as Box<dyn Widget>)
+                })
+                .collect(),
+        )
+    }
+
+    fn group(n: usize, focus: usize) -> Group {
+        Group::new((0..n).map(|_| Cell::One(Dummy::wrap())).collect()).with_initial_focus(focus)
+    }
+
+    #[test]
+    fn remove_before_focus() {
+        let mut group = group(4, 2);
+        assert!(group.remove(0).is_some());
+        assert_eq!(group.focus_index(), 1);
+    }
+
+    #[test]
+    fn remove_after_focus() {
+        let mut group = group(4, 1);
+        assert!(group.remove(3).is_some());
+        assert_eq!(group.focus_index(), 1);
+    }
+
+    #[test]
+    fn remove_focused_moves_to_next() {
+        let mut group = group(4, 1);
+        assert!(group.remove(1).is_some());
+        assert_eq!(group.focus_index(), 1);
+        assert_eq!(group.cells.len(), 3);
+    }
+
+    #[test]
+    fn remove_focused_last_moves_to_new_last() {
+        let mut group = group(3, 2);
+        assert!(group.remove(2).is_some());
+        assert_eq!(group.focus_index(), 1);
+    }
+
+    #[test]
+    fn remove_only_cell() {
+        let mut group = group(1, 0);
+        assert!(group.remove(0).is_some());
+        assert_eq!(group.focus_index(), 0);
+        assert!(group.focused().is_none());
+    }
+
+    #[test]
+    fn remove_out_of_range() {
+        let mut group = group(2, 1);
+        assert!(group.remove(2).is_none());
+        assert_eq!(group.focus_index(), 1);
+    }
+
+    #[test]
+    fn insert_keeps_focus() {
+        let mut group = group(3, 1);
+        group.insert(1, Cell::One(Dummy::wrap()));
+        assert_eq!(group.focus_index(), 2);
+        group.insert(10, Cell::One(Dummy::wrap()));
+        assert_eq!(group.focus_index(), 2);
+        assert_eq!(group.cells.len(), 5);
+    }
+
+    #[test]
+    fn replace_keeps_focus() {
+        let mut group = group(3, 1);
+        assert!(group.replace(1, Cell::Fixed(1.0, Dummy::wrap())).is_some());
+        assert!(group.replace(3, Cell::One(Dummy::wrap())).is_none());
+        assert_eq!(group.focus_index(), 1);
+        assert!(matches!(group.cells[1], Cell::Fixed(..)));
+    }
+
+    #[test]
+    fn tab_order_follows_insert_and_remove() {
+        let mut group = group(4, 0);
+        group.tab_order = Some(vec![3, 1, 2]);
+        group.insert(2, Cell::One(Dummy::wrap()));
+        assert_eq!(group.tab_order, Some(vec![4, 1, 3]));
+        group.remove(1);
+        assert_eq!(group.tab_order, Some(vec![3, 2]));
+        group.set_focus(3);
+        group.focus_next();
+        assert_eq!(group.focus_index(), 2);
+        group.focus_next();
+        assert_eq!(group.focus_index(), 3);
+    }
+
+    fn assert_tiles(split: &[(f32, f32)], start: f32, size: f32) {
+        let mut position = start;
+        for (cell_start, cell_size) in split {
+            assert_eq!(*cell_start, position);
+            position += cell_size;
+        }
+        assert_eq!(position, start + size);
+    }
+
+    #[test]
+    fn split_into_three_tiles_exactly() {
+        let group = group(3, 0);
+        let split = group.split(0.0, 100.0);
+        assert_tiles(&split, 0.0, 100.0);
+        let mixed = Group::new(vec![
+            Cell::Many(2, Dummy::wrap()),
+            Cell::Fixed(7.0, Dummy::wrap()),
+            Cell::One(Dummy::wrap()),
+        ]);
+        assert_tiles(&mixed.split(3.0, 100.0), 3.0, 100.0);
+    }
+
+    #[test]
+    fn split_remainder_goes_to_first_cells() {
+        assert_eq!(
+            group(3, 0).split(0.0, 100.0),
+            [(0.0, 34.0), (34.0, 33.0), (67.0, 33.0)]
+        );
+        assert_eq!(
+            group(4, 0).split(0.0, 101.0),
+            [(0.0, 26.0), (26.0, 25.0), (51.0, 25.0), (76.0, 25.0)]
+        );
+    }
+
+    #[test]
+    fn split_negative_size() {
+        let group = Group::new(vec![
+            Cell::One(Dummy::wrap()),
+            Cell::Fixed(-3.0, Dummy::wrap()),
+            Cell::Many(2, Dummy::wrap()),
+        ]);
+        assert_eq!(
+            group.split(5.0, -10.0),
+            [(5.0, 0.0), (5.0, 0.0), (5.0, 0.0)]
+        );
+        assert_eq!(group.split(0.0, 9.0), [(0.0, 3.0), (3.0, 0.0), (3.0, 6.0)]);
+    }
+
+    #[test]
+    fn split_overflowing_fixed_cells() {
+        let group = Group::new(vec![
+            Cell::Fixed(6.0, Dummy::wrap()),
+            Cell::One(Dummy::wrap()),
+            Cell::Fixed(6.0, Dummy::wrap()),
+        ]);
+        assert_eq!(group.split(0.0, 10.0), [(0.0, 6.0), (6.0, 0.0), (6.0, 4.0)]);
+        assert_eq!(group.split(0.0, 0.0), [(0.0, 0.0), (0.0, 0.0), (0.0, 0.0)]);
+    }
+
+    #[test]
+    fn remove_focused_stays_in_tab_order() {
+        let mut group = group(5, 1);
+        group.tab_order = Some(vec![1, 3, 4]);
+        group.remove(1);
+        assert_eq!(group.tab_order, Some(vec![2, 3]));
+        assert_eq!(group.focus_index(), 2);
+
+        group.set_focus(3);
+        group.remove(3);
+        assert_eq!(group.focus_index(), 2);
+    }
+
+    #[test]
+    fn remove_focused_outside_tab_order() {
+        let mut group = group(5, 1);
+        group.tab_order = Some(vec![0, 3]);
+        group.remove(1);
+        assert_eq!(group.focus_index(), 2);
+    }
+
+    #[test]
+    fn focus_changes_are_notified() {
+        let log = Log::default();
+        let mut context = Rcui::new();
+        let mut group = focusable(3, &log);
+        group.sync_focus(&mut context);
+        group.focus_next();
+        group.sync_focus(&mut context);
+        group.sync_focus(&mut context);
+        assert_eq!(*log.borrow(), [(0, "focus"), (0, "blur"), (1, "focus")]);
+
+        log.borrow_mut().clear();
+        group.insert(0, Cell::One(Dummy::wrap()));
+        group.sync_focus(&mut context);
+        assert!(log.borrow().is_empty());
+        group.remove(2);
+        group.sync_focus(&mut context);
+        assert_eq!(*log.borrow(), [(2, "focus")]);
+    }
 }
@@ -1,16 +1,156 @@
 use super::*;
+use std::cmp::Ordering;
+
 pub struct Window {
     pub offset: usize,
     pub height: usize,
 }
 
+/// How the window of the list follows the cursor
+#[derive(Clone, Copy, PartialEq)]
+pub enum ScrollPolicy {
+    /// Scrolls only when the cursor would leave the window
+    FollowSelection,
+    /// Keeps the cursor in the middle of the window where possible
+    CenterSelection,
+    /// Never scrolls on its own, the cursor may leave the window
+    Free,
+}
+
+/// What a mouse event did to the list, see `Window::handle_mouse`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MouseOutcome {
+    /// The event missed the window or means nothing to the list
+    Ignored,
+    /// The cursor may have moved or the window may have scrolled
+    Moved,
+    /// The item was double clicked
+    Activated(usize),
+}
+
+impl Window {
+    /// Resizes the window to `height` rows and moves it over the `len`
+    /// items the way `policy` wants it to follow the cursor
+    pub fn sync(&mut self, cursor: usize, len: usize, height: usize, policy: ScrollPolicy) {
+        self.height = height;
+        let max_offset = len.saturating_sub(height);
+
+        match policy {
+            ScrollPolicy::FollowSelection => {
+                if cursor >= self.offset + height {
+                    self.offset = cursor - height + 1;
+                } else if cursor < self.offset {
+                    self.offset = cursor;
+                }
+            }
+            ScrollPolicy::CenterSelection => {
+                self.offset = cursor.saturating_sub(height / 2).min(max_offset);
+            }
+            ScrollPolicy::Free => {
+                self.offset = self.offset.min(max_offset);
+            }
+        }
+    }
+
+    /// The cursor a window above the given one
+    pub fn page_up(&self, cursor: usize) -> usize {
+        cursor.saturating_sub(self.height)
+    }
+
+    /// The cursor a window below the given one, clamped to the `len` items
+    pub fn page_down(&self, cursor: usize, len: usize) -> usize {
+        (cursor + self.height).min(len.saturating_sub(1))
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.offset = self.offset.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self, len: usize) {
+        if self.offset + self.height < len {
+            self.offset += 1;
+        }
+    }
+
+    /// The index of the item at the screen position, given the window was
+    /// rendered into `rect`. The rows past the items are counted as well.
+    pub fn item_at(&self, rect: &Rect, x: i32, y: i32) -> Option<usize> {
+        let left = rect.x.floor() as i32;
+        let top = rect.y.floor() as i32;
+        let inside = x >= left
+            && x < left + rect.w.floor() as i32
+            && y >= top
+            && y < top + rect.h.floor() as i32;
+        if inside {
+            Some(self.offset + (y - top) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Clicking one of the `len` items of the window rendered into `rect`
+    /// moves the cursor to it and double clicking activates it. The wheel
+    /// scrolls the window with `ScrollPolicy::Free` and moves the cursor
+    /// otherwise.
+    pub fn handle_mouse(
+        &mut self,
+        rect: &Rect,
+        event: &Event,
+        cursor: &mut usize,
+        len: usize,
+        policy: ScrollPolicy,
+    ) -> MouseOutcome {
+        let (index, kind) = match event {
+            Event::Mouse { x, y, kind } => match self.item_at(rect, *x, *y) {
+                Some(index) => (index, *kind),
+                None => return MouseOutcome::Ignored,
+            },
+            _ => return MouseOutcome::Ignored,
+        };
+        match kind {
+            MouseKind::Click if index < len => *cursor = index,
+            MouseKind::DoubleClick if index < len => return MouseOutcome::Activated(index),
+            MouseKind::ScrollUp if policy == ScrollPolicy::Free => self.scroll_up(),
+            MouseKind::ScrollDown if policy == ScrollPolicy::Free => self.scroll_down(len),
+            MouseKind::ScrollUp => *cursor = cursor.saturating_sub(1),
+            MouseKind::ScrollDown => *cursor = (*cursor + 1).min(len.saturating_sub(1)),
+            _ => return MouseOutcome::Ignored,
+        }
+        MouseOutcome::Moved
+    }
+}
+
+pub type ItemStyle<T> = fn(&T, bool) -> (i16, attr_t);
+
 pub struct ItemList<T> {
     pub items: Vec<T>,
     pub cursor: usize,
     pub window: Window,
+    pub scroll_policy: ScrollPolicy,
+    /// Occurrences of this text in the items are rendered with
+    /// `style::MATCH_PAIR`
+    pub highlight: Option<String>,
+    /// Called when the cursor moves to another item
+    pub on_select: Option<fn(&T, &mut Rcui)>,
+    /// Called when Enter is pressed on an item
+    pub on_activate: Option<fn(&T, &mut Rcui)>,
+    /// Called with the new offset of the window when the list is rendered
+    /// scrolled differently than during the previous frame
+    pub on_scroll: Option<fn(usize, &mut Rcui)>,
+    /// Picks the pair and the attributes of an item given whether it's
+    /// under the cursor. The row under the cursor keeps the cursor pair and
+    /// only takes the attributes, so it stays readable.
+    pub style_for: Option<ItemStyle<T>>,
+    /// Renders a checkbox in front of every item and lets Space toggle the
+    /// item under the cursor
+    pub multi_select: bool,
+    selected: Vec<bool>,
+    scrolled_offset: usize,
+    rect: Rect,
+    inactive_pair: i16,
 }
 
-impl<T: ToString + Clone> ItemList<T> {
+impl<T: ToString> ItemList<T> {
     pub fn new(items: Vec<T>) -> Self {
         Self {
             items,
@@ -19,6 +159,17 @@ impl<T: ToString + Clone> ItemList<T> {
                 offset: 0,
                 height: 0,
             },
+            scroll_policy: ScrollPolicy::FollowSelection,
+            highlight: None,
+            on_select: None,
+            on_activate: None,
+            on_scroll: None,
+            style_for: None,
+            multi_select: false,
+            selected: Vec::new(),
+            scrolled_offset: 0,
+            rect: Rect::default(),
+            inactive_pair: style::INACTIVE_CURSOR_PAIR,
         }
     }
 
@@ -26,6 +177,17 @@ impl<T: ToString + Clone> ItemList<T> {
         Box::new(Self::new(items))
     }
 
+    /// The rectangle the list was rendered into during the last frame
+    pub fn last_rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    /// The pair of the cursor while the list is inactive.
+    /// `style::INACTIVE_CURSOR_PAIR` by default.
+    pub fn set_inactive_pair(&mut self, pair: i16) {
+        self.inactive_pair = pair;
+    }
+
     pub fn up(&mut self) {
         if self.cursor > 0 {
             self.cursor -= 1;
@@ -33,13 +195,7 @@ impl<T: ToString + Clone> ItemList<T> {
     }
 
     pub fn page_up(&mut self) {
-        for _ in 0..self.window.height {
-            self.up();
-
-            if self.cursor == 0 {
-                break;
-            }
-        }
+        self.cursor = self.window.page_up(self.cursor);
     }
 
     pub fn down(&mut self) {
@@ -50,31 +206,95 @@ impl<T: ToString + Clone> ItemList<T> {
     }
 
     pub fn page_down(&mut self) {
-        for _ in 0..self.window.height {
-            self.down();
+        self.cursor = self.window.page_down(self.cursor, self.items.len());
+    }
 
-            if self.cursor == self.items.len() - 1 {
-                break;
-            }
-        }
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = self.items.len().saturating_sub(1);
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.window.scroll_up();
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.window.scroll_down(self.items.len());
     }
 
     pub fn sync_window(&mut self, h: usize) {
-        self.window.height = h;
+        self.window
+            .sync(self.cursor, self.items.len(), h, self.scroll_policy);
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.get(index).copied().unwrap_or(false)
+    }
+
+    pub fn selected_indices(&self) -> Vec<usize> {
+        (0..self.items.len())
+            .filter(|i| self.is_selected(*i))
+            .collect()
+    }
 
-        if self.cursor >= self.window.offset + h {
-            self.window.offset = self.cursor - h + 1;
-        } else if self.cursor < self.window.offset {
-            self.window.offset = self.cursor;
+    /// Toggles the item under the cursor
+    pub fn toggle_selected(&mut self) {
+        if self.cursor < self.items.len() {
+            self.sync_selection();
+            self.selected[self.cursor] = !self.selected[self.cursor];
         }
     }
 
+    pub fn select_all(&mut self) {
+        self.selected = vec![true; self.items.len()];
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected.clear();
+    }
+
+    // The items are public, so the selection can fall out of their length
+    fn sync_selection(&mut self) {
+        self.selected.resize(self.items.len(), false);
+    }
+
     pub fn push(&mut self, item: T) {
         self.items.push(item)
     }
 
+    /// Inserts the item at the index (clamped to the length of the list)
+    /// keeping the cursor on the same item
+    pub fn insert(&mut self, index: usize, item: T) {
+        let index = index.min(self.items.len());
+        if !self.items.is_empty() && index <= self.cursor {
+            self.cursor += 1;
+        }
+        self.sync_selection();
+        self.selected.insert(index, false);
+        self.items.insert(index, item);
+    }
+
+    /// Inserts the item after the last one that is not greater than it, so
+    /// pushing into a list sorted by `compare` keeps it sorted
+    pub fn push_sorted_by<F>(&mut self, item: T, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering,
+    {
+        let index = self
+            .items
+            .iter()
+            .position(|x| compare(&item, x) == Ordering::Less)
+            .unwrap_or(self.items.len());
+        self.insert(index, item);
+    }
+
     pub fn remove(&mut self) -> Option<T> {
         if !self.items.is_empty() {
+            self.sync_selection();
+            self.selected.remove(self.cursor);
             let item = self.items.remove(self.cursor);
 
             if !self.items.is_empty() && self.cursor >= self.items.len() {
@@ -87,45 +307,473 @@ impl<T: ToString + Clone> ItemList<T> {
         }
     }
 
-    // TODO(#8): Operations to insert new items into the ItemList
+    /// Swaps the item under the cursor with the previous one keeping the
+    /// cursor on the moved item
+    pub fn move_selected_up(&mut self) {
+        if self.cursor > 0 && self.cursor < self.items.len() {
+            self.sync_selection();
+            self.selected.swap(self.cursor, self.cursor - 1);
+            self.items.swap(self.cursor, self.cursor - 1);
+            self.cursor -= 1;
+        }
+    }
+
+    /// Swaps the item under the cursor with the next one keeping the cursor
+    /// on the moved item
+    pub fn move_selected_down(&mut self) {
+        if self.cursor + 1 < self.items.len() {
+            self.sync_selection();
+            self.selected.swap(self.cursor, self.cursor + 1);
+            self.items.swap(self.cursor, self.cursor + 1);
+            self.cursor += 1;
+        }
+    }
+
+    /// Replaces the items keeping the cursor on the item with the same key
+    /// as the one under it before. If there is no such item the cursor
+    /// stays at the same index clamped to the new items. The checkbox
+    /// selection follows the keys the same way.
+    pub fn set_items_preserving_selection<K, F>(&mut self, items: Vec<T>, key: F)
+    where
+        K: PartialEq,
+        F: Fn(&T) -> K,
+    {
+        let cursor_key = self.items.get(self.cursor).map(&key);
+        let selected_keys: Vec<K> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.is_selected(*i))
+            .map(|(_, item)| key(item))
+            .collect();
+
+        self.items = items;
+        let keys: Vec<K> = self.items.iter().map(&key).collect();
+        self.selected = keys.iter().map(|k| selected_keys.contains(k)).collect();
+        match cursor_key.and_then(|cursor_key| keys.iter().position(|k| *k == cursor_key)) {
+            Some(cursor) => self.cursor = cursor,
+            None => self.cursor = self.cursor.min(self.items.len().saturating_sub(1)),
+        }
+    }
+
+    /// Stable sorts the items keeping the cursor and the selection on the
+    /// same items
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering,
+    {
+        let mut order: Vec<usize> = (0..self.items.len()).collect();
+        order.sort_by(|a, b| compare(&self.items[*a], &self.items[*b]));
+
+        self.sync_selection();
+        let mut items: Vec<Option<T>> = self.items.drain(..).map(Some).collect();
+        self.items = order.iter().filter_map(|i| items[*i].take()).collect();
+        self.selected = order.iter().map(|i| self.selected[*i]).collect();
+        if let Some(cursor) = order.iter().position(|i| *i == self.cursor) {
+            self.cursor = cursor;
+        }
+    }
+
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
     // TODO(#9): Operations to remove items from ItemList
 }
 
-impl<T: ToString + Clone> Widget for ItemList<T> {
-    fn render(&mut self, _context: &mut Rcui, rect: &Rect, active: bool) {
+impl<T: ToString> ItemList<T> {
+    /// Clicking an item moves the cursor to it and double clicking
+    /// activates it
+    fn handle_mouse(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        let cursor = self.cursor;
+        let outcome = self.window.handle_mouse(
+            &self.rect,
+            event,
+            &mut self.cursor,
+            self.items.len(),
+            self.scroll_policy,
+        );
+        match outcome {
+            MouseOutcome::Ignored => return false,
+            MouseOutcome::Moved => {
+                if let (Some(on_select), true) = (self.on_select, self.cursor != cursor) {
+                    on_select(&self.items[self.cursor], context);
+                }
+            }
+            MouseOutcome::Activated(index) => {
+                if let Some(on_activate) = self.on_activate {
+                    on_activate(&self.items[index], context);
+                }
+            }
+        }
+        true
+    }
+}
+
+impl<T: ToString> Widget for ItemList<T> {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        self.rect = *rect;
+        if rect.is_empty() {
+            return;
+        }
+
         let h = rect.h.floor() as usize;
         if h > 0 {
             self.sync_window(h);
+            if self.window.offset != self.scrolled_offset {
+                self.scrolled_offset = self.window.offset;
+                if let Some(on_scroll) = self.on_scroll {
+                    on_scroll(self.window.offset, context);
+                }
+            }
             for i in 0..h {
                 if self.window.offset + i < self.items.len() {
                     let selected = i + self.window.offset == self.cursor;
+                    let (item_pair, item_attrs) = match self.style_for {
+                        Some(style_for) => style_for(&self.items[i + self.window.offset], selected),
+                        None => (0, A_NORMAL()),
+                    };
                     let color_pair = if selected {
                         if active {
                             style::CURSOR_PAIR
                         } else {
-                            style::INACTIVE_CURSOR_PAIR
+                            self.inactive_pair
                         }
+                    } else if item_pair != 0 {
+                        item_pair
                     } else {
                         style::REGULAR_PAIR
                     };
 
-                    attron(COLOR_PAIR(color_pair));
                     let x = rect.x.floor() as i32;
                     let y = (rect.y + i as f32).floor() as i32;
                     let w = rect.w.floor() as usize;
-                    mv(y, x);
-                    let text = self.items[i + self.window.offset].to_string();
-                    if text.len() >= w {
-                        addstr(text.get(..w).unwrap_or(&text));
-                    } else {
-                        addstr(&text);
-                        for _ in 0..w - text.len() {
-                            addstr(" ");
+                    let index = i + self.window.offset;
+                    let checkbox = match (self.multi_select, self.is_selected(index)) {
+                        (false, _) => "",
+                        (true, true) => "[x] ",
+                        (true, false) => "[ ] ",
+                    };
+                    let checkbox: String = checkbox.chars().take(w).collect();
+                    let w = w - checkbox.chars().count();
+                    let text = self.items[index].to_string();
+                    let mut line: String = text.chars().take(w).collect();
+                    let n = line.chars().count();
+                    line.push_str(&" ".repeat(w - n));
+
+                    let highlight = self.highlight.as_ref().filter(|p| !p.is_empty());
+                    context.with_style(color_pair, item_attrs, |context| {
+                        mv(y, x);
+                        addstr(&checkbox);
+                        let mut rest = 0;
+                        if let Some(pattern) = highlight {
+                            for (start, found) in line.match_indices(pattern.as_str()) {
+                                addstr(&line[rest..start]);
+                                context
+                                    .with_style(style::MATCH_PAIR, A_NORMAL(), |_| addstr(found));
+                                rest = start + found.len();
+                            }
                         }
-                    }
-                    attroff(COLOR_PAIR(color_pair));
+                        addstr(&line[rest..]);
+                    });
+                }
+            }
+        }
+    }
+
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        if let Event::Mouse { .. } = event {
+            return self.handle_mouse(context, event);
+        }
+
+        if let Some(key) = event.key() {
+            let cursor = self.cursor;
+            match key {
+                Key::Arrow(Dir::Up) | Key::Char('k') => self.up(),
+                Key::Arrow(Dir::Down) | Key::Char('j') => self.down(),
+                Key::PageUp => self.page_up(),
+                Key::PageDown => self.page_down(),
+                Key::Home => self.home(),
+                Key::End => self.end(),
+                Key::Char(' ') if self.multi_select => self.toggle_selected(),
+                Key::Enter => match (self.on_activate, self.items.get(self.cursor)) {
+                    (Some(on_activate), Some(item)) => on_activate(item, context),
+                    _ => return false,
+                },
+                _ => return false,
+            }
+
+            if self.cursor != cursor {
+                if let (Some(on_select), Some(item)) = (self.on_select, self.items.get(self.cursor))
+                {
+                    on_select(item, context);
                 }
             }
+            true
+        } else {
+            false
+        }
+    }
+
+    fn measure(&self, _available: &Rect) -> (f32, f32) {
+        let w = self
+            .items
+            .iter()
+            .map(|item| item.to_string().chars().count())
+            .max()
+            .unwrap_or(0);
+        let checkbox = if self.multi_select { 4 } else { 0 };
+        ((w + checkbox) as f32, self.items.len() as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(items: &[&'static str], cursor: usize) -> ItemList<&'static str> {
+        let mut list = ItemList::new(items.to_vec());
+        list.cursor = cursor;
+        list
+    }
+
+    #[test]
+    fn enter_is_consumed_only_with_on_activate() {
+        let mut context = Rcui::new();
+        let enter = Event::KeyStroke(10);
+        let mut list = list(&["a", "b"], 1);
+        assert!(!list.handle_event(&mut context, &enter));
+        list.on_activate = Some(|item, context| {
+            context.push_event(Event::Custom(Box::new(*item)));
+        });
+        assert!(list.handle_event(&mut context, &enter));
+        match context.event_queue.pop_front() {
+            Some(Event::Custom(item)) => assert_eq!(item.downcast_ref(), Some(&"b")),
+            _ => panic!("on_activate was not called"),
         }
+        list.items.clear();
+        assert!(!list.handle_event(&mut context, &enter));
+    }
+
+    #[test]
+    fn remove_from_empty() {
+        let mut list = list(&[], 0);
+        assert_eq!(list.remove(), None);
+        assert_eq!(list.cursor, 0);
+    }
+
+    #[test]
+    fn remove_single_item() {
+        let mut list = list(&["a"], 0);
+        assert_eq!(list.remove(), Some("a"));
+        assert!(list.items.is_empty());
+        assert_eq!(list.cursor, 0);
+    }
+
+    #[test]
+    fn remove_first() {
+        let mut list = list(&["a", "b", "c"], 0);
+        assert_eq!(list.remove(), Some("a"));
+        assert_eq!(list.items, ["b", "c"]);
+        assert_eq!(list.cursor, 0);
+    }
+
+    #[test]
+    fn remove_middle() {
+        let mut list = list(&["a", "b", "c"], 1);
+        assert_eq!(list.remove(), Some("b"));
+        assert_eq!(list.items, ["a", "c"]);
+        assert_eq!(list.cursor, 1);
+    }
+
+    #[test]
+    fn remove_last() {
+        let mut list = list(&["a", "b", "c"], 2);
+        assert_eq!(list.remove(), Some("c"));
+        assert_eq!(list.items, ["a", "b"]);
+        assert_eq!(list.cursor, 1);
+    }
+
+    #[test]
+    fn remove_keeps_selection_in_step() {
+        let mut list = list(&["a", "b", "c"], 2);
+        list.toggle_selected();
+        list.cursor = 0;
+        list.remove();
+        assert_eq!(list.selected_indices(), [1]);
+        assert_eq!(list.items[1], "c");
+    }
+
+    #[test]
+    fn insert_before_cursor() {
+        let mut list = list(&["a", "b", "c"], 1);
+        list.insert(0, "x");
+        assert_eq!(list.items, ["x", "a", "b", "c"]);
+        assert_eq!(list.items[list.cursor], "b");
+    }
+
+    #[test]
+    fn insert_at_cursor() {
+        let mut list = list(&["a", "b", "c"], 1);
+        list.insert(1, "x");
+        assert_eq!(list.items, ["a", "x", "b", "c"]);
+        assert_eq!(list.items[list.cursor], "b");
+    }
+
+    #[test]
+    fn insert_after_cursor() {
+        let mut list = list(&["a", "b", "c"], 1);
+        list.insert(5, "x");
+        assert_eq!(list.items, ["a", "b", "c", "x"]);
+        assert_eq!(list.items[list.cursor], "b");
+    }
+
+    #[test]
+    fn insert_into_empty() {
+        let mut list = list(&[], 0);
+        list.insert(0, "x");
+        assert_eq!(list.items, ["x"]);
+        assert_eq!(list.cursor, 0);
+    }
+
+    #[test]
+    fn insert_moves_selection() {
+        let mut list = list(&["a", "b"], 1);
+        list.toggle_selected();
+        list.insert(0, "x");
+        assert_eq!(list.selected_indices(), [2]);
+    }
+
+    #[test]
+    fn push_sorted_after_equal_items() {
+        let mut list = list(&["a", "c", "c", "e"], 2);
+        list.push_sorted_by("c", |a, b| a.cmp(b));
+        list.push_sorted_by("z", |a, b| a.cmp(b));
+        assert_eq!(list.items, ["a", "c", "c", "c", "e", "z"]);
+        assert_eq!(list.cursor, 2);
+    }
+
+    #[test]
+    fn page_down_on_empty() {
+        let mut list = list(&[], 0);
+        list.window.height = 5;
+        list.page_down();
+        list.page_up();
+        assert_eq!(list.cursor, 0);
+    }
+
+    #[test]
+    fn move_selected_up() {
+        let mut list = list(&["a", "b", "c"], 2);
+        list.move_selected_up();
+        assert_eq!(list.items, ["a", "c", "b"]);
+        assert_eq!(list.cursor, 1);
+        list.move_selected_up();
+        list.move_selected_up();
+        assert_eq!(list.items, ["c", "a", "b"]);
+        assert_eq!(list.cursor, 0);
+    }
+
+    #[test]
+    fn move_selected_down() {
+        let mut list = list(&["a", "b", "c"], 0);
+        list.move_selected_down();
+        assert_eq!(list.items, ["b", "a", "c"]);
+        assert_eq!(list.cursor, 1);
+        list.move_selected_down();
+        list.move_selected_down();
+        assert_eq!(list.items, ["b", "c", "a"]);
+        assert_eq!(list.cursor, 2);
+    }
+
+    #[test]
+    fn move_selected_on_empty() {
+        let mut list = list(&[], 0);
+        list.move_selected_up();
+        list.move_selected_down();
+        assert!(list.items.is_empty());
+        assert_eq!(list.cursor, 0);
+    }
+
+    #[test]
+    fn sort_keeps_cursor_on_item() {
+        let mut list = list(&["d", "b", "a", "c"], 1);
+        list.sort();
+        assert_eq!(list.items, ["a", "b", "c", "d"]);
+        assert_eq!(list.items[list.cursor], "b");
+    }
+
+    #[test]
+    fn sort_is_stable_and_moves_selection() {
+        let mut list = list(&["b2", "a1", "b1", "a2"], 2);
+        list.toggle_selected();
+        list.sort_by(|a, b| a[..1].cmp(&b[..1]));
+        assert_eq!(list.items, ["a1", "a2", "b2", "b1"]);
+        assert_eq!(list.items[list.cursor], "b1");
+        assert_eq!(list.selected_indices(), [3]);
+    }
+
+    #[test]
+    fn window_follows_cursor_by_policy() {
+        let mut window = Window {
+            offset: 0,
+            height: 0,
+        };
+        window.sync(7, 20, 5, ScrollPolicy::FollowSelection);
+        assert_eq!(window.offset, 3);
+        window.sync(1, 20, 5, ScrollPolicy::FollowSelection);
+        assert_eq!(window.offset, 1);
+        window.sync(18, 20, 5, ScrollPolicy::CenterSelection);
+        assert_eq!(window.offset, 15);
+        window.sync(0, 4, 5, ScrollPolicy::Free);
+        assert_eq!(window.offset, 0);
+    }
+
+    #[test]
+    fn window_handles_mouse() {
+        let mut window = Window {
+            offset: 2,
+            height: 3,
+        };
+        let rect = Rect {
+            x: 1.0,
+            y: 1.0,
+            w: 4.0,
+            h: 3.0,
+        };
+        let mouse = |x, y, kind| Event::Mouse { x, y, kind };
+        let mut cursor = 2;
+        let policy = ScrollPolicy::FollowSelection;
+
+        let click = mouse(2, 3, MouseKind::Click);
+        assert_eq!(
+            window.handle_mouse(&rect, &click, &mut cursor, 10, policy),
+            MouseOutcome::Moved
+        );
+        assert_eq!(cursor, 4);
+        let outside = mouse(5, 3, MouseKind::Click);
+        assert_eq!(
+            window.handle_mouse(&rect, &outside, &mut cursor, 10, policy),
+            MouseOutcome::Ignored
+        );
+        let past_items = mouse(2, 3, MouseKind::DoubleClick);
+        assert_eq!(
+            window.handle_mouse(&rect, &past_items, &mut cursor, 4, policy),
+            MouseOutcome::Ignored
+        );
+        assert_eq!(
+            window.handle_mouse(&rect, &past_items, &mut cursor, 10, policy),
+            MouseOutcome::Activated(4)
+        );
+
+        let wheel = mouse(1, 1, MouseKind::ScrollDown);
+        window.handle_mouse(&rect, &wheel, &mut cursor, 5, policy);
+        window.handle_mouse(&rect, &wheel, &mut cursor, 5, policy);
+        assert_eq!((cursor, window.offset), (4, 2));
+        window.handle_mouse(&rect, &wheel, &mut cursor, 10, ScrollPolicy::Free);
+        assert_eq!((cursor, window.offset), (4, 3));
     }
 }
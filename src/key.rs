@@ -0,0 +1,124 @@
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// A decoded `Event::KeyStroke`. The codes that don't have a better name,
+/// like the separate bytes of multibyte UTF-8 characters, are kept as
+/// `Key::Raw`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Key {
+    Char(char),
+    Ctrl(char),
+    Fn(u8),
+    Arrow(Dir),
+    Enter,
+    Tab,
+    BackTab,
+    Backspace,
+    Delete,
+    Insert,
+    Esc,
+    /// The key pressed with Alt. Enter comes as `'\n'`.
+    Alt(char),
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Raw(i32),
+}
+
+impl Key {
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            9 => Self::Tab,
+            10 | 13 | KEY_ENTER => Self::Enter,
+            27 => Self::Esc,
+            8 | 127 | KEY_BACKSPACE => Self::Backspace,
+            1..=26 => Self::Ctrl((b'a' + code as u8 - 1) as char),
+            32..=126 => Self::Char(code as u8 as char),
+            KEY_UP => Self::Arrow(Dir::Up),
+            KEY_DOWN => Self::Arrow(Dir::Down),
+            KEY_LEFT => Self::Arrow(Dir::Left),
+            KEY_RIGHT => Self::Arrow(Dir::Right),
+            KEY_BTAB => Self::BackTab,
+            KEY_DC => Self::Delete,
+            KEY_IC => Self::Insert,
+            KEY_HOME => Self::Home,
+            KEY_END => Self::End,
+            KEY_PPAGE => Self::PageUp,
+            KEY_NPAGE => Self::PageDown,
+            code if (KEY_F0..=KEY_F0 + 63).contains(&code) => Self::Fn((code - KEY_F0) as u8),
+            code => Self::Raw(code),
+        }
+    }
+}
+
+impl Event {
+    /// Decodes the key of `Event::KeyStroke` or `Event::AltKeyStroke`. Use
+    /// the raw code of the event for anything `Key` has no name for.
+    pub fn key(&self) -> Option<Key> {
+        match self {
+            Self::KeyStroke(code) => Some(Key::from_code(*code)),
+            Self::AltKeyStroke(13) => Some(Key::Alt('\n')),
+            Self::AltKeyStroke(code) => Some(Key::Alt(*code as u8 as char)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_key_strokes() {
+        let table = [
+            (1, Key::Ctrl('a')),
+            (3, Key::Ctrl('c')),
+            (8, Key::Backspace),
+            (9, Key::Tab),
+            (10, Key::Enter),
+            (13, Key::Enter),
+            (26, Key::Ctrl('z')),
+            (27, Key::Esc),
+            (32, Key::Char(' ')),
+            (126, Key::Char('~')),
+            (127, Key::Backspace),
+            (KEY_BACKSPACE, Key::Backspace),
+            (KEY_ENTER, Key::Enter),
+            (KEY_BTAB, Key::BackTab),
+            (KEY_UP, Key::Arrow(Dir::Up)),
+            (KEY_RIGHT, Key::Arrow(Dir::Right)),
+            (KEY_DC, Key::Delete),
+            (KEY_IC, Key::Insert),
+            (KEY_HOME, Key::Home),
+            (KEY_END, Key::End),
+            (KEY_PPAGE, Key::PageUp),
+            (KEY_NPAGE, Key::PageDown),
+            (KEY_F0, Key::Fn(0)),
+            (KEY_F0 + 12, Key::Fn(12)),
+            (KEY_F0 + 63, Key::Fn(63)),
+            (KEY_F0 + 64, Key::Raw(KEY_F0 + 64)),
+            (0, Key::Raw(0)),
+            (0xC3, Key::Raw(0xC3)),
+        ];
+        for (code, key) in table.iter() {
+            assert_eq!(Event::KeyStroke(*code).key(), Some(*key), "code {}", code);
+        }
+    }
+
+    #[test]
+    fn decode_alt_key_strokes() {
+        assert_eq!(Event::AltKeyStroke('x' as i32).key(), Some(Key::Alt('x')));
+        assert_eq!(Event::AltKeyStroke('X' as i32).key(), Some(Key::Alt('X')));
+        assert_eq!(Event::AltKeyStroke(13).key(), Some(Key::Alt('\n')));
+        assert_eq!(Event::Resize.key(), None);
+        assert_eq!(Event::Paste("x".to_string()).key(), None);
+    }
+}
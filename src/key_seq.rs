@@ -0,0 +1,204 @@
+use super::*;
+use std::time::{Duration, Instant};
+
+pub type KeyAction<T> = fn(&mut T, &mut Rcui);
+
+/// Binds sequences of keys like `gg` to actions on the origin. The keys
+/// that can't start or continue any of the sequences go to the origin right
+/// away. The keys of an unfinished sequence are held back until it's
+/// either completed or broken by a key that doesn't belong to it, in which
+/// case the held back keys are passed to the origin as usual.
+///
+/// If the next key comes later than `timeout` after the previous one the
+/// unfinished sequence is given up on before the key is handled: the held
+/// back keys fire the sequence they complete, if any, or go to the origin.
+pub struct KeySeq<T> {
+    pub origin: T,
+    pub bindings: Vec<(Vec<i32>, KeyAction<T>)>,
+    pub timeout: Duration,
+    pending: Vec<i32>,
+    last_key: Option<Instant>,
+}
+
+impl<T: Widget> KeySeq<T> {
+    pub fn new(origin: T, bindings: Vec<(&str, KeyAction<T>)>) -> Self {
+        Self {
+            origin,
+            bindings: bindings
+                .into_iter()
+                .map(|(keys, action)| (keys.chars().map(|c| c as i32).collect(), action))
+                .collect(),
+            timeout: Duration::from_millis(500),
+            pending: Vec::new(),
+            last_key: None,
+        }
+    }
+
+    pub fn wrap(origin: T, bindings: Vec<(&str, KeyAction<T>)>) -> Box<Self> {
+        Box::new(Self::new(origin, bindings))
+    }
+
+    fn exact_match(&self, keys: &[i32]) -> Option<KeyAction<T>> {
+        self.bindings
+            .iter()
+            .find(|(seq, _)| seq.as_slice() == keys)
+            .map(|(_, action)| *action)
+    }
+
+    fn can_continue(&self, keys: &[i32]) -> bool {
+        self.bindings
+            .iter()
+            .any(|(seq, _)| seq.len() > keys.len() && seq.starts_with(keys))
+    }
+
+    /// Fires the binding the held back keys complete or passes them to the
+    /// origin otherwise
+    fn flush(&mut self, context: &mut Rcui) {
+        let held = std::mem::take(&mut self.pending);
+        if held.is_empty() {
+            return;
+        }
+        match self.exact_match(&held) {
+            Some(action) => action(&mut self.origin, context),
+            None => {
+                for held_key in held {
+                    self.origin
+                        .handle_event(context, &Event::KeyStroke(held_key));
+                }
+            }
+        }
+    }
+
+    fn handle_key(&mut self, context: &mut Rcui, key: i32) -> bool {
+        self.pending.push(key);
+
+        if self.can_continue(&self.pending) {
+            return true;
+        }
+
+        if let Some(action) = self.exact_match(&self.pending) {
+            self.pending.clear();
+            action(&mut self.origin, context);
+            return true;
+        }
+
+        self.pending.pop();
+        if !self.pending.is_empty() {
+            self.flush(context);
+            self.handle_key(context, key)
+        } else {
+            self.origin.handle_event(context, &Event::KeyStroke(key))
+        }
+    }
+}
+
+impl<T: Widget> Widget for KeySeq<T> {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        context.render_widget(&mut self.origin, rect, active);
+    }
+
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        if let Event::KeyStroke(key) = event {
+            let now = Instant::now();
+            if let Some(last_key) = self.last_key {
+                if now.duration_since(last_key) > self.timeout {
+                    self.flush(context);
+                }
+            }
+            self.last_key = Some(now);
+            self.handle_key(context, *key)
+        } else {
+            self.origin.handle_event(context, event)
+        }
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        self.origin.measure(available)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACTION_G: i32 = -1;
+    const ACTION_GG: i32 = -2;
+
+    #[derive(Default)]
+    struct Recorder {
+        keys: Vec<i32>,
+    }
+
+    impl Widget for Recorder {
+        fn handle_event(&mut self, _context: &mut Rcui, event: &Event) -> bool {
+            if let Event::KeyStroke(key) = event {
+                self.keys.push(*key);
+            }
+            true
+        }
+    }
+
+    fn key_seq(bindings: Vec<(&str, KeyAction<Recorder>)>) -> KeySeq<Recorder> {
+        KeySeq::new(Recorder::default(), bindings)
+    }
+
+    fn press(seq: &mut KeySeq<Recorder>, context: &mut Rcui, keys: &str) {
+        for key in keys.chars() {
+            seq.handle_event(context, &Event::KeyStroke(key as i32));
+        }
+    }
+
+    fn pause(seq: &mut KeySeq<Recorder>) {
+        seq.last_key = Instant::now().checked_sub(seq.timeout * 2);
+    }
+
+    #[test]
+    fn sequence_fires_action() {
+        let mut context = Rcui::new();
+        let mut seq = key_seq(vec![("gg", |r, _| r.keys.push(ACTION_GG))]);
+        press(&mut seq, &mut context, "agg");
+        assert_eq!(seq.origin.keys, ['a' as i32, ACTION_GG]);
+    }
+
+    #[test]
+    fn mismatch_passes_held_keys() {
+        let mut context = Rcui::new();
+        let mut seq = key_seq(vec![("gg", |r, _| r.keys.push(ACTION_GG))]);
+        press(&mut seq, &mut context, "gx");
+        assert_eq!(seq.origin.keys, ['g' as i32, 'x' as i32]);
+    }
+
+    #[test]
+    fn mismatch_fires_shorter_binding() {
+        let mut context = Rcui::new();
+        let mut seq = key_seq(vec![
+            ("g", |r, _| r.keys.push(ACTION_G)),
+            ("gg", |r, _| r.keys.push(ACTION_GG)),
+        ]);
+        press(&mut seq, &mut context, "gx");
+        assert_eq!(seq.origin.keys, [ACTION_G, 'x' as i32]);
+    }
+
+    #[test]
+    fn timeout_passes_held_keys() {
+        let mut context = Rcui::new();
+        let mut seq = key_seq(vec![("gg", |r, _| r.keys.push(ACTION_GG))]);
+        press(&mut seq, &mut context, "g");
+        pause(&mut seq);
+        press(&mut seq, &mut context, "x");
+        assert_eq!(seq.origin.keys, ['g' as i32, 'x' as i32]);
+    }
+
+    #[test]
+    fn timeout_fires_shorter_binding() {
+        let mut context = Rcui::new();
+        let mut seq = key_seq(vec![
+            ("g", |r, _| r.keys.push(ACTION_G)),
+            ("gg", |r, _| r.keys.push(ACTION_GG)),
+        ]);
+        press(&mut seq, &mut context, "g");
+        pause(&mut seq);
+        press(&mut seq, &mut context, "gg");
+        assert_eq!(seq.origin.keys, [ACTION_G, ACTION_GG]);
+    }
+}
@@ -0,0 +1,228 @@
+use super::*;
+
+/// A list of `len` items that are not stored anywhere. The provider is
+/// asked for the items only while they're visible, so the size of the list
+/// doesn't matter. The items the provider returns `None` for are rendered
+/// as empty rows.
+pub struct LazyList<T> {
+    pub provider: Box<dyn Fn(usize) -> Option<T>>,
+    pub len: usize,
+    pub cursor: usize,
+    pub window: Window,
+    pub scroll_policy: ScrollPolicy,
+    /// Called with the index of the item when Enter is pressed on it
+    pub on_activate: Option<fn(usize, &mut Rcui)>,
+    rect: Rect,
+    inactive_pair: i16,
+}
+
+impl<T: ToString> LazyList<T> {
+    pub fn new<F>(len: usize, provider: F) -> Self
+    where
+        F: Fn(usize) -> Option<T> + 'static,
+    {
+        Self {
+            provider: Box::new(provider),
+            len,
+            cursor: 0,
+            window: Window {
+                offset: 0,
+                height: 0,
+            },
+            scroll_policy: ScrollPolicy::FollowSelection,
+            on_activate: None,
+            rect: Rect::default(),
+            inactive_pair: style::INACTIVE_CURSOR_PAIR,
+        }
+    }
+
+    pub fn wrap<F>(len: usize, provider: F) -> Box<Self>
+    where
+        F: Fn(usize) -> Option<T> + 'static,
+    {
+        Box::new(Self::new(len, provider))
+    }
+
+    /// See `ItemList::last_rect`
+    pub fn last_rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    /// See `ItemList::set_inactive_pair`
+    pub fn set_inactive_pair(&mut self, pair: i16) {
+        self.inactive_pair = pair;
+    }
+
+    /// Changes the amount of items keeping the cursor within them
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len;
+        self.cursor = self.cursor.min(len.saturating_sub(1));
+    }
+
+    pub fn up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        if self.cursor + 1 < self.len {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn page_up(&mut self) {
+        self.cursor = self.window.page_up(self.cursor);
+    }
+
+    pub fn page_down(&mut self) {
+        self.cursor = self.window.page_down(self.cursor, self.len);
+    }
+
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = self.len.saturating_sub(1);
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.window.scroll_up();
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.window.scroll_down(self.len);
+    }
+
+    pub fn sync_window(&mut self, h: usize) {
+        self.window
+            .sync(self.cursor, self.len, h, self.scroll_policy);
+    }
+
+    /// The visible items with their indices, the provider is not asked for
+    /// the rest
+    fn visible_rows(&self) -> Vec<(usize, String)> {
+        let end = self.len.min(self.window.offset + self.window.height);
+        (self.window.offset..end)
+            .map(|index| {
+                let text = (self.provider)(index)
+                    .map(|item| item.to_string())
+                    .unwrap_or_default();
+                (index, text)
+            })
+            .collect()
+    }
+}
+
+impl<T: ToString> Widget for LazyList<T> {
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
+        self.rect = *rect;
+        if rect.is_empty() {
+            return;
+        }
+
+        let h = rect.h.floor() as usize;
+        let w = rect.w.floor() as usize;
+        let x = rect.x.floor() as i32;
+        self.sync_window(h);
+        for (i, (index, text)) in self.visible_rows().into_iter().enumerate() {
+            let color_pair = if index != self.cursor {
+                style::REGULAR_PAIR
+            } else if active {
+                style::CURSOR_PAIR
+            } else {
+                self.inactive_pair
+            };
+
+            let mut line: String = text.chars().take(w).collect();
+            let n = line.chars().count();
+            line.push_str(&" ".repeat(w - n));
+
+            let y = (rect.y + i as f32).floor() as i32;
+            context.with_style(color_pair, A_NORMAL(), |_| mvaddstr(y, x, &line));
+        }
+    }
+
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        let outcome = self.window.handle_mouse(
+            &self.rect,
+            event,
+            &mut self.cursor,
+            self.len,
+            self.scroll_policy,
+        );
+        match outcome {
+            MouseOutcome::Ignored => {}
+            MouseOutcome::Moved => return true,
+            MouseOutcome::Activated(index) => {
+                if let Some(on_activate) = self.on_activate {
+                    on_activate(index, context);
+                }
+                return true;
+            }
+        }
+
+        match event.key() {
+            Some(Key::Arrow(Dir::Up)) | Some(Key::Char('k')) => self.up(),
+            Some(Key::Arrow(Dir::Down)) | Some(Key::Char('j')) => self.down(),
+            Some(Key::PageUp) => self.page_up(),
+            Some(Key::PageDown) => self.page_down(),
+            Some(Key::Home) => self.home(),
+            Some(Key::End) => self.end(),
+            Some(Key::Enter) => match self.on_activate {
+                Some(on_activate) if self.cursor < self.len => on_activate(self.cursor, context),
+                _ => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        // Measuring the items would mean asking for all of them
+        (available.w, self.len as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn enter_is_consumed_only_with_on_activate() {
+        let mut context = Rcui::new();
+        let enter = Event::KeyStroke(10);
+        let mut list = LazyList::new(2, Some);
+        assert!(!list.handle_event(&mut context, &enter));
+        list.on_activate = Some(|_, context| context.quit());
+        assert!(list.handle_event(&mut context, &enter));
+        assert!(!context.event_queue.is_empty());
+        list.set_len(0);
+        assert!(!list.handle_event(&mut context, &enter));
+    }
+
+    #[test]
+    fn provider_is_asked_only_for_visible_items() {
+        let asked = Rc::new(RefCell::new(Vec::new()));
+        let mut list = LazyList::new(1_000_000, {
+            let asked = asked.clone();
+            move |index| {
+                asked.borrow_mut().push(index);
+                Some(index)
+            }
+        });
+        list.cursor = 500_000;
+        list.sync_window(3);
+        let rows = list.visible_rows();
+        assert_eq!(rows[0], (499_998, "499998".to_string()));
+        assert_eq!(*asked.borrow(), [499_998, 499_999, 500_000]);
+
+        asked.borrow_mut().clear();
+        list.set_len(3);
+        list.home();
+        list.sync_window(5);
+        list.visible_rows();
+        assert_eq!(*asked.borrow(), [0, 1, 2]);
+    }
+}
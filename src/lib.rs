@@ -1,9 +1,19 @@
+mod border;
+mod center;
 mod column;
+mod cond;
 pub mod curses;
+mod dialog;
 mod dummy;
 mod edit_field;
+mod field;
+mod filter;
+mod form;
 mod group;
 mod item_list;
+mod key;
+mod key_seq;
+mod lazy_list;
 mod proxy;
 mod row;
 pub mod style;
@@ -12,18 +22,31 @@ mod text;
 use curses::CURSOR_VISIBILITY::*;
 use curses::*;
 use std::collections::VecDeque;
+use std::io::{self, Write};
 use std::panic::{set_hook, take_hook};
+use std::time::{Duration, Instant};
 
+pub use self::border::*;
+pub use self::center::*;
 pub use self::column::*;
+pub use self::cond::*;
+pub use self::dialog::*;
 pub use self::dummy::*;
 pub use self::edit_field::*;
+pub use self::field::*;
+pub use self::filter::*;
+pub use self::form::*;
 pub use self::group::*;
 pub use self::item_list::*;
+pub use self::key::*;
+pub use self::key_seq::*;
+pub use self::lazy_list::*;
 pub use self::proxy::*;
 pub use self::row::*;
 pub use self::text::*;
 pub use std::any::Any;
 
+#[derive(Clone, Copy, Default, PartialEq)]
 pub struct Rect {
     pub x: f32,
     pub y: f32,
@@ -31,15 +54,71 @@ pub struct Rect {
     pub h: f32,
 }
 
+impl Rect {
+    /// Whether there is not a single whole cell to draw in
+    pub fn is_empty(&self) -> bool {
+        self.w < 1.0 || self.h < 1.0
+    }
+}
+
 pub enum Event {
     Quit,
     KeyStroke(i32),
+    /// A key stroke with the Alt (Meta) modifier. The terminals send it as
+    /// an Esc followed by the key, see `Options::escape_delay`.
+    AltKeyStroke(i32),
+    /// The terminal was resized. Unlike the rest of the events it's
+    /// delivered to all of the widgets, including the inactive ones and the
+    /// layers, and always before the next frame is rendered, so the widgets
+    /// can recompute their cached layout.
+    Resize,
+    /// Text pasted into the terminal as a whole. Only delivered when
+    /// `Options::bracketed_paste` is enabled and the terminal supports it,
+    /// otherwise the pasted text comes as separate key strokes.
+    Paste(String),
+    /// Only delivered when `Options::mouse` is enabled. `x` and `y` are the
+    /// cell on the screen.
+    Mouse {
+        x: i32,
+        y: i32,
+        kind: MouseKind,
+    },
+    /// Sent by `Group` to the cell that got the focus. The containers
+    /// notice the change the next time they handle an event or render, so
+    /// it comes before either reaches the cell.
+    Focus,
+    /// Sent by `Group` to the cell that lost the focus, see `Event::Focus`
+    Blur,
     Custom(Box<dyn Any>),
 }
 
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MouseKind {
+    /// The left button was pressed
+    Click,
+    /// The left button was pressed on the same cell again within
+    /// `Options::double_click_interval`. It comes right after the `Click`
+    /// of the second press.
+    DoubleClick,
+    Release,
+    ScrollUp,
+    ScrollDown,
+}
+
 pub trait Widget {
+    /// The containers render their widgets through `Rcui::render_widget`
     fn render(&mut self, _context: &mut Rcui, _rect: &Rect, _active: bool) {}
-    fn handle_event(&mut self, _context: &mut Rcui, _event: &Event) {}
+    /// Returns whether the widget has consumed the event, so the widget
+    /// that passed it down can handle the ignored ones itself.
+    fn handle_event(&mut self, _context: &mut Rcui, _event: &Event) -> bool {
+        false
+    }
+
+    /// The preferred width and height of the widget when it's given the
+    /// `available` space. By default the widget wants all of it.
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        (available.w, available.h)
+    }
 }
 
 pub fn screen_rect() -> Rect {
@@ -54,9 +133,318 @@ pub fn screen_rect() -> Rect {
     }
 }
 
+/// Fills the rectangle with spaces of `style::REGULAR_PAIR`. The style
+/// it was called with is restored afterwards.
+pub fn clear_rect(context: &mut Rcui, rect: &Rect) {
+    let x = rect.x.floor() as i32;
+    let y = rect.y.floor() as i32;
+    let w = rect.w.floor() as usize;
+    let h = rect.h.floor() as i32;
+    let blank = " ".repeat(w);
+    context.with_style(style::REGULAR_PAIR, A_NORMAL(), |_| {
+        for row in 0..h {
+            mv(y + row, x);
+            addstr(&blank);
+        }
+    });
+}
+
+/// How the terminal driver hands the input over
+#[derive(Clone, Copy, PartialEq)]
+pub enum InputMode {
+    /// Leaves the mode `initscr()` has set up
+    Inherited,
+    /// The keys come right away, but Ctrl+C and the like still send the
+    /// signals
+    Cbreak,
+    /// The keys come right away, including Ctrl+C, Ctrl+Z and the like
+    Raw,
+}
+
+#[derive(Clone)]
+pub struct Options {
+    /// How long the event loop waits for the input before waking up again.
+    /// rcui has no ticks or timers yet, so it only affects how quickly the
+    /// loop notices the things that are polled rather than delivered as
+    /// key strokes (like the terminal resize on Windows) and how much CPU
+    /// it burns while idle.
+    pub poll_timeout: Duration,
+    /// Caps how many identical key strokes in a row are processed per
+    /// frame. The extra repeats that piled up in the input buffer while a
+    /// key was held are dropped, so the navigation does not overshoot after
+    /// the key is released. Keep in mind that it applies to the typed or
+    /// pasted text as well.
+    pub max_key_repeat: Option<usize>,
+    /// Asks the terminal to mark the pasted text, so it's delivered as
+    /// a single `Event::Paste`. Ignored on Windows.
+    pub bracketed_paste: bool,
+    /// How long to wait for the rest of the sequence after an Esc. The keys
+    /// that arrive within it are delivered as `Event::AltKeyStroke` and a
+    /// lone Esc is delivered only after it runs out, so it's the latency of
+    /// the Esc key. Too short of a delay may split the Alt combos or the
+    /// function keys on slow connections. Ignored on Windows.
+    pub escape_delay: Duration,
+    /// Ctrl+C and Ctrl+V, which `EditField` copies and pastes with, only
+    /// reach the widgets in `InputMode::Raw`. In the other modes the
+    /// terminal sends SIGINT on Ctrl+C and may quote the next key on Ctrl+V.
+    pub input_mode: InputMode,
+    /// Whether the typed keys are echoed by the terminal
+    pub echo: bool,
+    /// Whether the function and arrow keys are decoded into the KEY_* codes
+    pub keypad: bool,
+    pub cursor_visible: bool,
+    /// Reports the mouse as `Event::Mouse`
+    pub mouse: bool,
+    pub double_click_interval: Duration,
+    /// Caps how many events are handled between two frames, so the handlers
+    /// that keep pushing new events can't stop the UI from being rendered.
+    /// The rest of the events are handled after the next frame. At least
+    /// one event is always handled, so 0 behaves like 1.
+    pub max_events_per_frame: usize,
+    /// Called with the events none of the layers nor the main widget has
+    /// consumed, except for `Event::Quit` and `Event::Resize`
+    pub on_unhandled: Option<fn(&mut Rcui, &Event)>,
+    /// The smallest screen (width and height) the UI is usable on. On the
+    /// smaller screens a message asking for a bigger terminal is shown
+    /// instead of the UI until the terminal is resized. The key strokes,
+    /// the pastes and the mouse don't reach the widgets meanwhile and go
+    /// straight to `on_unhandled`, so it can still quit the app.
+    pub min_size: Option<(f32, f32)>,
+    /// Collects the `FrameStats` of every frame, see `Rcui::frame_stats`
+    pub diagnostics: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            poll_timeout: Duration::from_millis(10),
+            max_key_repeat: None,
+            bracketed_paste: true,
+            escape_delay: Duration::from_millis(25),
+            input_mode: InputMode::Inherited,
+            echo: true,
+            keypad: true,
+            cursor_visible: false,
+            mouse: false,
+            double_click_interval: Duration::from_millis(400),
+            max_events_per_frame: 1024,
+            on_unhandled: None,
+            min_size: None,
+            diagnostics: false,
+        }
+    }
+}
+
+/// What it took to render a frame. rcui draws straight into curses, so
+/// the amount of the cells written is not known. The amount of the styled
+/// draws is the closest there is to it.
+#[derive(Clone, Copy, Default)]
+pub struct FrameStats {
+    pub render_time: Duration,
+    /// The events handled right before the frame
+    pub events: usize,
+    /// The calls of `Rcui::with_style`
+    pub styled_draws: usize,
+    /// The calls of `Rcui::erase`, the full erases of the screen
+    pub erases: usize,
+    /// The calls of `Rcui::render_widget`. The containers of rcui render
+    /// their widgets through it, the widgets calling `Widget::render`
+    /// directly are not counted.
+    pub renders: usize,
+}
+
+const ESC: i32 = 27;
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+fn starts_with_bytes(keys: &[i32], bytes: &[u8]) -> bool {
+    keys.len() >= bytes.len() && keys.iter().zip(bytes).all(|(k, b)| *k == *b as i32)
+}
+
+/// Whether the keys are the beginning of `bytes` cut short by the end of
+/// the input read so far
+fn is_prefix_of_bytes(keys: &[i32], bytes: &[u8]) -> bool {
+    keys.len() < bytes.len() && keys.iter().zip(bytes).all(|(k, b)| *k == *b as i32)
+}
+
+fn init_terminal(options: &Options) {
+    keypad(stdscr(), options.keypad);
+    match options.input_mode {
+        InputMode::Inherited => {}
+        InputMode::Cbreak => {
+            cbreak();
+        }
+        InputMode::Raw => {
+            raw();
+        }
+    }
+    if options.echo {
+        echo();
+    } else {
+        noecho();
+    }
+    curs_set(if options.cursor_visible {
+        CURSOR_VISIBLE
+    } else {
+        CURSOR_INVISIBLE
+    });
+    if options.mouse {
+        mousemask(ALL_MOUSE_EVENTS as mmask_t, None);
+        // The clicks are told apart by rcui itself, so ncurses doesn't have
+        // to hold the presses back waiting for the release
+        mouseinterval(0);
+    }
+    if options.bracketed_paste {
+        set_bracketed_paste(true);
+    }
+}
+
+/// Undoes whatever `init_terminal` has changed, so the shell gets the
+/// terminal back the way it was
+fn restore_terminal(options: &Options) {
+    if options.bracketed_paste {
+        set_bracketed_paste(false);
+    }
+    if options.mouse {
+        mousemask(0, None);
+    }
+    curs_set(CURSOR_VISIBLE);
+    if !options.echo {
+        echo();
+    }
+    match options.input_mode {
+        InputMode::Inherited => {}
+        InputMode::Cbreak => {
+            nocbreak();
+        }
+        InputMode::Raw => {
+            noraw();
+        }
+    }
+    if options.keypad {
+        keypad(stdscr(), false);
+    }
+    endwin();
+}
+
+/// getch() returns ERR both when it runs out of the time and when there is
+/// no input anymore (like when the terminal is gone), so the latter is
+/// checked separately to not spin forever on it. A regular file given as
+/// stdin is always readable and can't be told apart this way.
+#[cfg(unix)]
+fn input_closed() -> bool {
+    let mut stdin = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let ready = unsafe { libc::poll(&mut stdin, 1, 0) };
+    ready > 0 && stdin.revents & (libc::POLLHUP | libc::POLLERR | libc::POLLNVAL) != 0
+}
+
+#[cfg(windows)]
+fn input_closed() -> bool {
+    false
+}
+
+/// Where the event loop reads the keys from, so it can be driven without a
+/// terminal
+trait Input {
+    /// The next key or ERR when there is none yet
+    fn getch(&mut self) -> i32;
+    /// Whether the keys have stopped coming for good
+    fn closed(&mut self) -> bool;
+}
+
+struct Terminal;
+
+impl Input for Terminal {
+    fn getch(&mut self) -> i32 {
+        getch()
+    }
+
+    fn closed(&mut self) -> bool {
+        input_closed()
+    }
+}
+
+#[cfg(unix)]
+fn set_bracketed_paste(enabled: bool) {
+    let sequence = if enabled {
+        "\x1b[?2004h"
+    } else {
+        "\x1b[?2004l"
+    };
+    print!("{}", sequence);
+    let _ = io::stdout().flush();
+}
+
+/// The Windows console doesn't understand the sequences and prints them
+#[cfg(windows)]
+fn set_bracketed_paste(_enabled: bool) {}
+
+/// What's drawn over everything below a layer before the layer itself
+#[derive(Clone, Copy, PartialEq)]
+pub enum Scrim {
+    /// Leaves everything below as it is
+    None,
+    /// Dims the whole screen. The colors below are replaced with
+    /// `style::REGULAR_PAIR`.
+    Dim,
+    /// Recolors the whole screen with the pair
+    Pair(i16),
+}
+
+impl Scrim {
+    fn render(&self, screen: &Rect) {
+        let (attrs, pair) = match self {
+            Self::None => return,
+            Self::Dim => (A_DIM(), style::REGULAR_PAIR),
+            Self::Pair(pair) => (A_NORMAL(), *pair),
+        };
+        let (attrs, pair) = if style::colors_enabled() {
+            (attrs, pair)
+        } else {
+            (attrs | style::pair_attrs(pair), 0)
+        };
+        for y in 0..screen.h.floor() as i32 {
+            mvchgat(y, 0, -1, attrs, pair);
+        }
+    }
+}
+
+struct Layer {
+    widget: Box<dyn Widget>,
+    scrim: Scrim,
+}
+
+enum LayerOp {
+    Push(Layer),
+    Pop,
+}
+
 pub struct Rcui {
     event_queue: VecDeque<Event>,
     clipboard: Vec<char>,
+    layers: Vec<Layer>,
+    layer_ops: Vec<LayerOp>,
+    screen: Rect,
+    paste: Option<Vec<u8>>,
+    /// The keys that may be the start of a paste marker split between two
+    /// reads of the input
+    partial_keys: Vec<i32>,
+    started: Instant,
+    frame: u64,
+    elapsed: Duration,
+    styles: Vec<(i16, attr_t)>,
+    on_unhandled: Option<fn(&mut Rcui, &Event)>,
+    min_size: Option<(f32, f32)>,
+    stats: Option<FrameStats>,
+    last_stats: Option<FrameStats>,
+    stats_overlay: bool,
+    last_click: Option<(i32, i32, Instant)>,
+    #[cfg(feature = "clipboard")]
+    system_clipboard: Option<arboard::Clipboard>,
 }
 
 impl Rcui {
@@ -64,35 +452,458 @@ impl Rcui {
         Self {
             event_queue: VecDeque::new(),
             clipboard: Vec::new(),
+            layers: Vec::new(),
+            layer_ops: Vec::new(),
+            screen: Rect::default(),
+            paste: None,
+            partial_keys: Vec::new(),
+            started: Instant::now(),
+            frame: 0,
+            elapsed: Duration::default(),
+            styles: Vec::new(),
+            on_unhandled: None,
+            min_size: None,
+            stats: None,
+            last_stats: None,
+            stats_overlay: false,
+            last_click: None,
+            #[cfg(feature = "clipboard")]
+            system_clipboard: None,
         }
     }
 
+    /// The events are handled in the order they were pushed, including the
+    /// ones pushed by the handlers of the other events, and all of them are
+    /// handled before the next frame unless more than
+    /// `Options::max_events_per_frame` pile up.
     pub fn push_event(&mut self, event: Event) {
         self.event_queue.push_back(event);
     }
 
+    /// Layers are rendered on top of the main widget in the order they were
+    /// pushed. Only the topmost layer is active. The events go to the
+    /// layers from the top down and then to the main widget, until one of
+    /// them consumes the event, so a layer can leave the keys it doesn't
+    /// care about to the widgets below.
+    ///
+    /// The layer stack is modified after the current event or frame is
+    /// done, so a layer can safely pop itself from its own handler.
+    pub fn push_layer(&mut self, layer: Box<dyn Widget>) {
+        self.push_layer_with_scrim(layer, Scrim::None);
+    }
+
+    /// Pushes the layer drawing the scrim over the main widget and the
+    /// layers below it first. The scrim is drawn anew every frame, so it
+    /// goes away along with the layer.
+    pub fn push_layer_with_scrim(&mut self, layer: Box<dyn Widget>, scrim: Scrim) {
+        self.layer_ops.push(LayerOp::Push(Layer {
+            widget: layer,
+            scrim,
+        }));
+    }
+
+    pub fn pop_layer(&mut self) {
+        self.layer_ops.push(LayerOp::Pop);
+    }
+
+    pub fn layers_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    fn apply_layer_ops(&mut self) {
+        for op in self.layer_ops.drain(..) {
+            match op {
+                LayerOp::Push(layer) => self.layers.push(layer),
+                LayerOp::Pop => {
+                    self.layers.pop();
+                }
+            }
+        }
+    }
+
+    /// The screen rectangle of the current frame. Unlike [`screen_rect`] it
+    /// is only updated once per frame, including after a resize.
+    pub fn screen(&self) -> Rect {
+        self.screen
+    }
+
+    /// The number of the frame being rendered, starting from 0
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// The time since the start of the event loop, taken once at the
+    /// beginning of each frame so all of the widgets see the same value
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Runs `draw` with the pair and the attributes applied, restoring the
+    /// previous style once it returns. The nested styles add their
+    /// attributes to the outer ones, and the pair 0 keeps the outer pair.
+    pub fn with_style<F, R>(&mut self, pair: i16, attrs: attr_t, draw: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        if let Some(stats) = self.stats.as_mut() {
+            stats.styled_draws += 1;
+        }
+        let (outer_pair, outer_attrs) = self.current_style();
+        let pair = if pair == 0 { outer_pair } else { pair };
+        self.styles.push((pair, outer_attrs | attrs));
+        self.apply_style();
+        let result = draw(self);
+        self.styles.pop();
+        self.apply_style();
+        result
+    }
+
+    fn current_style(&self) -> (i16, attr_t) {
+        self.styles.last().copied().unwrap_or((0, A_NORMAL()))
+    }
+
+    fn apply_style(&self) {
+        let (pair, attrs) = self.current_style();
+        attrset(style::pair_attrs(pair) | attrs);
+    }
+
+    /// Renders the widget, counting it in `FrameStats::renders`. The
+    /// containers should render their widgets through it.
+    pub fn render_widget(&mut self, widget: &mut dyn Widget, rect: &Rect, active: bool) {
+        if let Some(stats) = self.stats.as_mut() {
+            stats.renders += 1;
+        }
+        widget.render(self, rect, active);
+    }
+
+    /// Clears the whole screen, counting it in `FrameStats::erases`
+    pub fn erase(&mut self) {
+        if let Some(stats) = self.stats.as_mut() {
+            stats.erases += 1;
+        }
+        erase();
+    }
+
+    /// The stats of the previous frame. Only collected with
+    /// `Options::diagnostics` enabled.
+    pub fn frame_stats(&self) -> Option<FrameStats> {
+        self.last_stats
+    }
+
+    /// Shows the stats of every frame in its own top right corner. The
+    /// overlay is drawn after the widgets, so its own draw is not counted.
+    /// Does nothing without `Options::diagnostics`.
+    pub fn toggle_stats_overlay(&mut self) {
+        self.stats_overlay = !self.stats_overlay;
+    }
+
+    fn render_stats_overlay(&mut self) {
+        if let (true, Some(stats)) = (self.stats_overlay, self.last_stats) {
+            let text = format!(
+                " {}us, {} events, {} styled draws, {} erases, {} renders ",
+                stats.render_time.as_micros(),
+                stats.events,
+                stats.styled_draws,
+                stats.erases,
+                stats.renders
+            );
+            let x = (self.screen.w as i32 - text.chars().count() as i32).max(0);
+            self.with_style(style::REGULAR_PAIR, A_REVERSE(), |_| mvaddstr(0, x, &text));
+        }
+    }
+
+    fn render_frame(&mut self, ui: &mut dyn Widget) {
+        let started = Instant::now();
+        self.render_frame_widgets(ui);
+        if let Some(mut stats) = self.stats.take() {
+            stats.render_time = started.elapsed();
+            self.last_stats = Some(stats);
+            self.render_stats_overlay();
+            self.stats = Some(FrameStats::default());
+        }
+    }
+
+    fn render_frame_widgets(&mut self, ui: &mut dyn Widget) {
+        // Whatever style a widget has left on is not carried over
+        self.styles.clear();
+        self.apply_style();
+        self.elapsed = self.started.elapsed();
+        self.screen = screen_rect();
+        let rect = self.screen;
+        if let Some((w, h)) = self.min_size.filter(|_| self.too_small()) {
+            let mut message = Text::new(&format!("Terminal too small (need {}x{})", w, h));
+            message.halign = HAlign::Centre;
+            message.valign = VAlign::Centre;
+            self.render_widget(&mut message, &rect, false);
+            self.apply_layer_ops();
+            self.frame += 1;
+            return;
+        }
+        let mut layers = std::mem::take(&mut self.layers);
+        self.render_widget(ui, &rect, layers.is_empty());
+        let n = layers.len();
+        for (i, layer) in layers.iter_mut().enumerate() {
+            layer.scrim.render(&rect);
+            self.render_widget(layer.widget.as_mut(), &rect, i + 1 == n);
+        }
+        self.layers = layers;
+        self.apply_layer_ops();
+        self.frame += 1;
+    }
+
+    fn too_small(&self) -> bool {
+        match self.min_size {
+            Some((w, h)) => self.screen.w < w || self.screen.h < h,
+            None => false,
+        }
+    }
+
+    fn dispatch_event(&mut self, ui: &mut dyn Widget, event: &Event) {
+        if self.too_small() {
+            if let Event::KeyStroke(_)
+            | Event::AltKeyStroke(_)
+            | Event::Paste(_)
+            | Event::Mouse { .. } = event
+            {
+                if let Some(on_unhandled) = self.on_unhandled {
+                    on_unhandled(self, event);
+                }
+                self.apply_layer_ops();
+                return;
+            }
+        }
+
+        if let Event::Resize = event {
+            let mut layers = std::mem::take(&mut self.layers);
+            ui.handle_event(self, event);
+            for layer in layers.iter_mut() {
+                layer.widget.handle_event(self, event);
+            }
+            self.layers = layers;
+            self.apply_layer_ops();
+            return;
+        }
+
+        let mut layers = std::mem::take(&mut self.layers);
+        let consumed = layers
+            .iter_mut()
+            .rev()
+            .any(|layer| layer.widget.handle_event(self, event))
+            || ui.handle_event(self, event);
+        self.layers = layers;
+        if let (false, Some(on_unhandled)) = (consumed, self.on_unhandled) {
+            if !matches!(event, Event::Quit) {
+                on_unhandled(self, event);
+            }
+        }
+        self.apply_layer_ops();
+    }
+
+    /// Handles the events from the queue until it's empty or `limit` of
+    /// them are handled. Returns whether `Event::Quit` was among them.
+    fn handle_events(&mut self, ui: &mut dyn Widget, limit: usize) -> bool {
+        let mut quit = false;
+        // Handling nothing would leave the queue non-empty forever, so the
+        // loop would never wait for the input again
+        for _ in 0..limit.max(1) {
+            match self.event_queue.pop_front() {
+                Some(event) => {
+                    if let Some(stats) = self.stats.as_mut() {
+                        stats.events += 1;
+                    }
+                    if let Event::Quit = event {
+                        quit = true;
+                    }
+
+                    self.dispatch_event(ui, &event);
+                }
+                None => break,
+            }
+        }
+        quit
+    }
+
+    /// An empty `keys` means no more input has come, so the keys held back
+    /// as a possible start of a paste marker are taken as they are.
+    /// Busy waits on the keys, unless there are events left over from the
+    /// previous frame, and takes everything that came. Quits once the input
+    /// is closed.
+    fn read_keys(&mut self, input: &mut dyn Input) -> Vec<i32> {
+        let mut key = input.getch();
+        while key == ERR && self.event_queue.is_empty() && self.partial_keys.is_empty() {
+            if input.closed() {
+                self.quit();
+                break;
+            }
+            key = input.getch();
+        }
+
+        let mut keys = Vec::new();
+        while key != ERR {
+            keys.push(key);
+            key = input.getch();
+        }
+        keys
+    }
+
+    fn push_keys(&mut self, keys: &[i32], options: &Options) {
+        let more_may_come = !keys.is_empty();
+        let mut all_keys = std::mem::take(&mut self.partial_keys);
+        all_keys.extend_from_slice(keys);
+        let keys = &all_keys[..];
+
+        let mut last_key = ERR;
+        let mut repeat = 0;
+        let mut i = 0;
+        while i < keys.len() {
+            let rest = &keys[i..];
+
+            if let Some(paste) = self.paste.as_mut() {
+                if more_may_come && is_prefix_of_bytes(rest, PASTE_END) {
+                    self.partial_keys = rest.to_vec();
+                    break;
+                }
+                if starts_with_bytes(rest, PASTE_END) {
+                    let text = String::from_utf8_lossy(paste).into_owned();
+                    self.paste = None;
+                    self.push_event(Event::Paste(text));
+                    i += PASTE_END.len();
+                } else {
+                    paste.push(rest[0] as u8);
+                    i += 1;
+                }
+                continue;
+            }
+
+            // A lone Esc is not held back, so it isn't delayed
+            if options.bracketed_paste
+                && more_may_come
+                && rest.len() > 1
+                && is_prefix_of_bytes(rest, PASTE_START)
+            {
+                self.partial_keys = rest.to_vec();
+                break;
+            }
+
+            if options.bracketed_paste && starts_with_bytes(rest, PASTE_START) {
+                self.paste = Some(Vec::new());
+                i += PASTE_START.len();
+                continue;
+            }
+
+            // ncurses has already turned the known escape sequences into the
+            // KEY_* codes, so an Esc followed by a plain byte is an Alt combo
+            if rest.len() > 1 && rest[0] == ESC && rest[1] != ESC && (0..=127).contains(&rest[1]) {
+                self.push_event(Event::AltKeyStroke(rest[1]));
+                last_key = ERR;
+                i += 2;
+                continue;
+            }
+
+            let key = rest[0];
+            repeat = if key == last_key { repeat + 1 } else { 1 };
+            last_key = key;
+            let allowed = match options.max_key_repeat {
+                Some(max) => repeat <= max,
+                None => true,
+            };
+            if key == KEY_RESIZE {
+                self.push_event(Event::Resize);
+            } else if key == KEY_MOUSE {
+                self.push_mouse(options);
+            } else if allowed {
+                self.push_event(Event::KeyStroke(key));
+            }
+            i += 1;
+        }
+    }
+
+    fn push_mouse(&mut self, options: &Options) {
+        let mut mouse = MEVENT {
+            id: 0,
+            x: 0,
+            y: 0,
+            z: 0,
+            bstate: 0,
+        };
+        if getmouse(&mut mouse) == ERR {
+            return;
+        }
+
+        let (x, y) = (mouse.x, mouse.y);
+        let pressed = |button| mouse.bstate & button as mmask_t != 0;
+        if pressed(BUTTON1_PRESSED) {
+            self.push_event(Event::Mouse {
+                x,
+                y,
+                kind: MouseKind::Click,
+            });
+            let now = Instant::now();
+            let double = matches!(self.last_click, Some((last_x, last_y, at))
+                if last_x == x && last_y == y
+                    && now.duration_since(at) <= options.double_click_interval);
+            if double {
+                self.last_click = None;
+                self.push_event(Event::Mouse {
+                    x,
+                    y,
+                    kind: MouseKind::DoubleClick,
+                });
+            } else {
+                self.last_click = Some((x, y, now));
+            }
+            return;
+        }
+
+        let kind = if pressed(BUTTON1_RELEASED) {
+            MouseKind::Release
+        } else if pressed(BUTTON4_PRESSED) {
+            MouseKind::ScrollUp
+        } else if pressed(BUTTON5_PRESSED) {
+            MouseKind::ScrollDown
+        } else {
+            return;
+        };
+        self.push_event(Event::Mouse { x, y, kind });
+    }
+
     // TODO(#36): no support for nested event loops via Rcui::exec()
 
-    pub fn exec(mut ui: Box<dyn Widget>) {
+    pub fn exec(ui: Box<dyn Widget>) {
+        Self::exec_with_options(ui, Options::default());
+    }
+
+    pub fn exec_with_options(mut ui: Box<dyn Widget>, options: Options) {
         let mut context = Self::new();
+        context.on_unhandled = options.on_unhandled;
+        context.min_size = options.min_size;
+        if options.diagnostics {
+            context.stats = Some(FrameStats::default());
+        }
 
         unsafe {
             libc::setlocale(libc::LC_ALL, "en_US.UTF-8\0".as_ptr().cast());
         }
 
         initscr();
-        keypad(stdscr(), true);
-        timeout(10);
+        timeout(options.poll_timeout.as_millis() as i32);
+        #[cfg(unix)]
+        set_escdelay(options.escape_delay.as_millis() as i32);
 
         style::init_style();
 
-        curs_set(CURSOR_INVISIBLE);
+        init_terminal(&options);
 
         set_hook(Box::new({
             let default_hook = take_hook();
+            let options = options.clone();
             move |payload| {
-                endwin();
+                restore_terminal(&options);
+                // Whatever is left in the stdout buffer would be mixed into
+                // the panic message otherwise
+                let _ = io::stdout().flush();
                 default_hook(payload);
+                let _ = io::stderr().flush();
             }
         }));
 
@@ -101,47 +912,349 @@ impl Rcui {
             #[cfg(windows)]
             if is_termresized() {
                 resize_term(0, 0);
+                context.dispatch_event(ui.as_mut(), &Event::Resize);
             }
-            erase();
-            ui.render(&mut context, &screen_rect(), true);
+            context.erase();
+            context.render_frame(ui.as_mut());
+            // Presenting the frame right away instead of leaving it to the
+            // first getch(), so the blank screen of initscr() is never shown
+            refresh();
 
-            // Busy waiting on the key event
-            let mut key = getch();
-            while key == ERR {
-                key = getch();
-            }
-
-            // Flushing everything we've got
-            while key != ERR {
-                context.push_event(Event::KeyStroke(key));
-                key = getch();
-            }
-
-            // Handling all of the events from the queue
-            while !context.event_queue.is_empty() {
-                if let Some(event) = context.event_queue.pop_front() {
-                    if let Event::Quit = event {
-                        quit = true;
-                    }
+            let keys = context.read_keys(&mut Terminal);
+            context.push_keys(&keys, &options);
 
-                    ui.handle_event(&mut context, &event);
-                };
-            }
+            quit = context.handle_events(ui.as_mut(), options.max_events_per_frame);
         }
 
-        endwin();
+        restore_terminal(&options);
     }
 
+    /// With the `clipboard` feature the text goes to the system clipboard
+    /// as well. Without it, or if the system clipboard is not available,
+    /// the clipboard is only shared between the widgets of the app.
     pub fn put_to_clipboard(&mut self, text: &[char]) {
         self.clipboard.clear();
         self.clipboard.extend_from_slice(text);
+        #[cfg(feature = "clipboard")]
+        if let Some(system) = self.system_clipboard() {
+            let _ = system.set_text(text.iter().collect::<String>());
+        }
     }
 
-    pub fn get_clipboard(&self) -> &[char] {
+    /// With the `clipboard` feature the text comes from the system
+    /// clipboard when it's available
+    pub fn get_clipboard(&mut self) -> &[char] {
+        #[cfg(feature = "clipboard")]
+        if let Some(text) = self
+            .system_clipboard()
+            .and_then(|system| system.get_text().ok())
+        {
+            self.clipboard = text.chars().collect();
+        }
         &self.clipboard
     }
 
+    #[cfg(feature = "clipboard")]
+    fn system_clipboard(&mut self) -> Option<&mut arboard::Clipboard> {
+        // Kept around since on some systems the copied text is gone as soon
+        // as the clipboard that owns it is dropped
+        if self.system_clipboard.is_none() {
+            self.system_clipboard = arboard::Clipboard::new().ok();
+        }
+        self.system_clipboard.as_mut()
+    }
+
     pub fn quit(&mut self) {
         self.push_event(Event::Quit);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_into_empty_rects() {
+        let mut context = Rcui::new();
+        let mut widgets: Vec<Box<dyn Widget>> = vec![
+            Dummy::wrap(),
+            Text::wrap("text"),
+            Box::new(Text::markdown("# text")),
+            ItemList::wrap(vec!["a", "b"]),
+            LazyList::wrap(10, Some),
+            EditField::wrap(),
+            Field::wrap("label:"),
+            Border::wrap(Text::wrap("text")),
+            Dialog::wrap(Text::wrap("text")),
+            Center::wrap(3.0, 3.0, Text::wrap("text")),
+            Center::fit_wrap(Text::wrap("text")),
+            Row::wrap(vec![
+                Cell::One(Text::wrap("a")),
+                Cell::Fixed(2.0, Dummy::wrap()),
+            ]),
+            Column::wrap(vec![
+                Cell::One(Text::wrap("a")),
+                Cell::Many(2, Dummy::wrap()),
+            ]),
+        ];
+        for rect in [
+            Rect::default(),
+            Rect {
+                w: 1.0,
+                ..Rect::default()
+            },
+            Rect {
+                h: 1.0,
+                ..Rect::default()
+            },
+        ] {
+            for widget in widgets.iter_mut() {
+                widget.render(&mut context, &rect, true);
+            }
+        }
+    }
+
+    #[test]
+    fn render_widget_counts_renders() {
+        let mut context = Rcui::new();
+        let mut ui = Filter::new(
+            |_, _, _| false,
+            Row::new(vec![
+                Cell::One(Dummy::wrap()),
+                Cell::One(Column::wrap(vec![
+                    Cell::One(Dummy::wrap()),
+                    Cell::One(Dummy::wrap()),
+                ])),
+            ]),
+        );
+        let rect = Rect {
+            x: 0.0,
+            y: 0.0,
+            w: 4.0,
+            h: 4.0,
+        };
+        context.render_widget(&mut ui, &rect, true);
+        assert!(context.stats.is_none());
+
+        context.stats = Some(FrameStats::default());
+        context.render_widget(&mut ui, &rect, true);
+        assert_eq!(context.stats.map(|stats| stats.renders), Some(6));
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        keys: Vec<i32>,
+    }
+
+    impl Widget for Recorder {
+        fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+            if let Event::KeyStroke(key) = event {
+                self.keys.push(*key);
+                // The events pushed by the handlers go after the queued ones
+                if *key == 1 {
+                    context.push_event(Event::KeyStroke(10));
+                }
+            }
+            true
+        }
+    }
+
+    #[test]
+    fn handle_events_in_order_up_to_limit() {
+        let mut context = Rcui::new();
+        let mut ui = Recorder::default();
+        for key in 1..=3 {
+            context.push_event(Event::KeyStroke(key));
+        }
+        assert!(!context.handle_events(&mut ui, 2));
+        assert_eq!(ui.keys, [1, 2]);
+        context.push_event(Event::Quit);
+        assert!(context.handle_events(&mut ui, 1024));
+        assert_eq!(ui.keys, [1, 2, 3, 10]);
+        assert!(context.event_queue.is_empty());
+    }
+
+    #[test]
+    fn handle_events_with_zero_limit() {
+        let mut context = Rcui::new();
+        let mut ui = Recorder::default();
+        context.quit();
+        assert!(context.handle_events(&mut ui, 0));
+    }
+
+    #[test]
+    fn input_skips_widgets_while_too_small() {
+        let mut context = Rcui::new();
+        let mut ui = Recorder::default();
+        context.min_size = Some((80.0, 24.0));
+        context.on_unhandled = Some(|context, event| {
+            if let Event::KeyStroke(113) = event {
+                context.quit();
+            }
+        });
+        context.push_event(Event::Mouse {
+            x: 0,
+            y: 0,
+            kind: MouseKind::Click,
+        });
+        context.push_event(Event::KeyStroke(113));
+        assert!(context.handle_events(&mut ui, 1024));
+        assert!(ui.keys.is_empty());
+    }
+
+    struct Consume(bool);
+
+    impl Widget for Consume {
+        fn handle_event(&mut self, _context: &mut Rcui, _event: &Event) -> bool {
+            self.0
+        }
+    }
+
+    #[test]
+    fn events_fall_through_layers() {
+        let mut context = Rcui::new();
+        let mut ui = Recorder::default();
+        context.push_layer(Box::new(Consume(false)));
+        context.apply_layer_ops();
+        context.push_event(Event::KeyStroke(1));
+        context.handle_events(&mut ui, 1024);
+        assert_eq!(ui.keys, [1, 10]);
+
+        context.push_layer(Box::new(Consume(true)));
+        context.push_layer(Box::new(Consume(false)));
+        context.apply_layer_ops();
+        context.push_event(Event::KeyStroke(2));
+        context.handle_events(&mut ui, 1024);
+        assert_eq!(ui.keys, [1, 10]);
+        assert_eq!(context.layers_count(), 3);
+    }
+
+    fn bytes(s: &str) -> Vec<i32> {
+        s.bytes().map(|b| b as i32).collect()
+    }
+
+    fn pushed(context: &mut Rcui) -> Vec<String> {
+        context
+            .event_queue
+            .drain(..)
+            .map(|event| match event {
+                Event::KeyStroke(key) => format!("key {}", key as u8 as char),
+                Event::AltKeyStroke(key) => format!("alt {}", key as u8 as char),
+                Event::Paste(text) => format!("paste {}", text),
+                _ => "other".to_string(),
+            })
+            .collect()
+    }
+
+    /// Hands out the keys, with ERR standing for a timed out getch(), and
+    /// then nothing but ERR
+    struct Script {
+        keys: Vec<i32>,
+        reads: usize,
+        closed: bool,
+    }
+
+    impl Script {
+        fn new(keys: &[i32], closed: bool) -> Self {
+            Self {
+                keys: keys.to_vec(),
+                reads: 0,
+                closed,
+            }
+        }
+    }
+
+    impl Input for Script {
+        fn getch(&mut self) -> i32 {
+            self.reads += 1;
+            self.keys.get(self.reads - 1).copied().unwrap_or(ERR)
+        }
+
+        fn closed(&mut self) -> bool {
+            self.closed && self.reads >= self.keys.len()
+        }
+    }
+
+    #[test]
+    fn read_keys_waits_for_input() {
+        let mut context = Rcui::new();
+        let mut input = Script::new(&[ERR, ERR, 1, 2, ERR, 3], false);
+        assert_eq!(context.read_keys(&mut input), [1, 2]);
+        assert_eq!(context.read_keys(&mut input), [3]);
+        assert!(context.event_queue.is_empty());
+    }
+
+    #[test]
+    fn read_keys_skips_waiting_with_events_left() {
+        let mut context = Rcui::new();
+        context.push_event(Event::Resize);
+        let mut input = Script::new(&[], false);
+        assert!(context.read_keys(&mut input).is_empty());
+        assert_eq!(input.reads, 1);
+    }
+
+    #[test]
+    fn closed_input_quits() {
+        let mut context = Rcui::new();
+        let mut ui = Recorder::default();
+        let mut input = Script::new(&[1, ERR, ERR], true);
+        assert_eq!(context.read_keys(&mut input), [1]);
+        context.push_keys(&[1], &Options::default());
+        assert!(!context.handle_events(&mut ui, 1024));
+
+        assert!(context.read_keys(&mut input).is_empty());
+        assert_eq!(input.reads, 3);
+        assert!(context.handle_events(&mut ui, 1024));
+    }
+
+    #[test]
+    fn push_keys_paste() {
+        let mut context = Rcui::new();
+        let options = Options::default();
+        context.push_keys(&bytes("a\x1b[200~b\x1bc\x1b[201~d"), &options);
+        assert_eq!(pushed(&mut context), ["key a", "paste b\x1bc", "key d"]);
+    }
+
+    #[test]
+    fn push_keys_paste_split_between_reads() {
+        let mut context = Rcui::new();
+        let options = Options::default();
+        context.push_keys(&bytes("\x1b[20"), &options);
+        assert!(pushed(&mut context).is_empty());
+        context.push_keys(&bytes("0~x\x1b[20"), &options);
+        context.push_keys(&bytes("1~y"), &options);
+        assert_eq!(pushed(&mut context), ["paste x", "key y"]);
+    }
+
+    #[test]
+    fn push_keys_partial_marker_without_more_input() {
+        let mut context = Rcui::new();
+        let options = Options::default();
+        context.push_keys(&bytes("\x1b["), &options);
+        assert!(pushed(&mut context).is_empty());
+        context.push_keys(&[], &options);
+        assert_eq!(pushed(&mut context), ["alt ["]);
+    }
+
+    #[test]
+    fn push_keys_alt() {
+        let mut context = Rcui::new();
+        let options = Options::default();
+        context.push_keys(&bytes("\x1bx\x1b\x1by\x1b"), &options);
+        assert_eq!(
+            pushed(&mut context),
+            ["alt x", "key \x1b", "alt y", "key \x1b"]
+        );
+    }
+
+    #[test]
+    fn push_keys_max_repeat() {
+        let mut context = Rcui::new();
+        let options = Options {
+            max_key_repeat: Some(2),
+            ..Options::default()
+        };
+        context.push_keys(&bytes("jjjjkj"), &options);
+        assert_eq!(pushed(&mut context), ["key j", "key j", "key k", "key j"]);
+    }
+}
@@ -1,5 +1,8 @@
 use super::*;
 
+/// Hands all of the events to the handler, which decides whether the origin
+/// sees them. Use `Filter` to handle a few events and leave the rest to the
+/// origin.
 pub struct Proxy<T> {
     pub origin: T,
     pub handler: fn(&mut T, &mut Rcui, &Event),
@@ -17,10 +20,17 @@ impl<T: Widget> Proxy<T> {
 
 impl<T: Widget> Widget for Proxy<T> {
     fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
-        self.origin.render(context, rect, active);
+        context.render_widget(&mut self.origin, rect, active);
     }
 
-    fn handle_event(&mut self, context: &mut Rcui, event: &Event) {
+    /// The handler takes over all of the events, so they are always
+    /// considered consumed
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
         (self.handler)(&mut self.origin, context, event);
+        true
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        self.origin.measure(available)
     }
 }
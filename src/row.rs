@@ -26,26 +26,38 @@ impl Row {
 
 impl Widget for Row {
     fn render(&mut self, context: &mut Rcui, rect: &Rect, active: bool) {
-        let n = self.group.cells.len();
-        let cell_size = self.group.cell_size(rect.w);
-        let mut x = rect.x;
-        for i in 0..n {
-            let widget_size = self.group.cells[i].size(cell_size);
-            self.group.cells[i].get_widget_mut().render(
-                context,
+        self.group.sync_focus(context);
+        if rect.is_empty() {
+            return;
+        }
+
+        let layout = self.group.split(rect.x, rect.w);
+        for (i, (x, w)) in layout.into_iter().enumerate() {
+            context.render_widget(
+                self.group.cells[i].get_widget_mut().as_mut(),
                 &Rect {
                     x,
                     y: rect.y,
-                    w: widget_size,
+                    w,
                     h: rect.h,
                 },
                 active && i == self.group.focus,
             );
-            x += widget_size;
         }
     }
 
-    fn handle_event(&mut self, context: &mut Rcui, event: &Event) {
-        self.group.handle_event(context, event);
+    fn handle_event(&mut self, context: &mut Rcui, event: &Event) -> bool {
+        self.group.handle_event(context, event)
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        self.group
+            .cells
+            .iter()
+            .map(|cell| match cell {
+                Cell::Fixed(size, widget) => (*size, widget.measure(available).1),
+                _ => cell.get_widget().measure(available),
+            })
+            .fold((0.0, 0.0), |(w, h), (cw, ch)| (w + cw, h.max(ch)))
     }
 }
@@ -2,16 +2,137 @@
 use ncurses::*;
 #[cfg(windows)]
 use pdcurses::*;
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub const REGULAR_PAIR: i16 = 1;
 pub const CURSOR_PAIR: i16 = 2;
 pub const INACTIVE_CURSOR_PAIR: i16 = 3;
 pub const SELECTION_PAIR: i16 = 4;
+pub const MATCH_PAIR: i16 = 5;
+pub const WARNING_PAIR: i16 = 6;
+
+pub struct LineSet {
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    pub tee_down: &'static str,
+    pub tee_up: &'static str,
+    pub tee_right: &'static str,
+    pub tee_left: &'static str,
+    pub cross: &'static str,
+}
+
+pub const UNICODE_LINES: LineSet = LineSet {
+    horizontal: "─",
+    vertical: "│",
+    top_left: "┌",
+    top_right: "┐",
+    bottom_left: "└",
+    bottom_right: "┘",
+    tee_down: "┬",
+    tee_up: "┴",
+    tee_right: "├",
+    tee_left: "┤",
+    cross: "┼",
+};
+
+pub const ASCII_LINES: LineSet = LineSet {
+    horizontal: "-",
+    vertical: "|",
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    tee_down: "+",
+    tee_up: "+",
+    tee_right: "+",
+    tee_left: "+",
+    cross: "+",
+};
+
+static ASCII_LINES_ACTIVE: AtomicBool = AtomicBool::new(false);
+static COLORS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// The line set all of the widgets draw their borders and separators with
+pub fn line_set() -> &'static LineSet {
+    if ASCII_LINES_ACTIVE.load(Ordering::Relaxed) {
+        &ASCII_LINES
+    } else {
+        &UNICODE_LINES
+    }
+}
+
+pub fn set_ascii_lines(ascii: bool) {
+    ASCII_LINES_ACTIVE.store(ascii, Ordering::Relaxed);
+}
+
+/// Checks the locale variables the same way the C library resolves them
+fn utf8_locale() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            let value = value.to_lowercase();
+            value.contains("utf-8") || value.contains("utf8")
+        })
+        .unwrap_or(false)
+}
+
+fn env_flag(name: &str) -> bool {
+    env::var_os(name).is_some_and(|value| !value.is_empty())
+}
+
+/// `RCUI_FORCE_COLOR` enables the colors even if the terminal doesn't
+/// report them, otherwise `NO_COLOR` (see https://no-color.org) disables
+/// them
+fn color_wanted() -> bool {
+    if env_flag("RCUI_FORCE_COLOR") {
+        true
+    } else if env_flag("NO_COLOR") {
+        false
+    } else {
+        has_colors()
+    }
+}
+
+pub fn colors_enabled() -> bool {
+    COLORS_ENABLED.load(Ordering::Relaxed)
+}
+
+/// The attributes to draw the pair with. Without the colors the pairs of
+/// rcui are approximated with the monochrome attributes and the rest of
+/// them are drawn as is.
+pub fn pair_attrs(pair: i16) -> attr_t {
+    if colors_enabled() {
+        return COLOR_PAIR(pair);
+    }
+
+    match pair {
+        CURSOR_PAIR | SELECTION_PAIR => A_REVERSE(),
+        INACTIVE_CURSOR_PAIR => A_REVERSE() | A_DIM(),
+        MATCH_PAIR => A_BOLD() | A_UNDERLINE(),
+        WARNING_PAIR => A_BOLD(),
+        _ => A_NORMAL(),
+    }
+}
 
 pub fn init_style() {
-    start_color();
-    init_pair(REGULAR_PAIR, COLOR_WHITE, COLOR_BLACK);
-    init_pair(CURSOR_PAIR, COLOR_BLACK, COLOR_WHITE);
-    init_pair(INACTIVE_CURSOR_PAIR, COLOR_BLACK, COLOR_CYAN);
-    init_pair(SELECTION_PAIR, COLOR_BLACK, COLOR_MAGENTA);
+    let colors = color_wanted();
+    COLORS_ENABLED.store(colors, Ordering::Relaxed);
+    if colors {
+        start_color();
+        init_pair(REGULAR_PAIR, COLOR_WHITE, COLOR_BLACK);
+        init_pair(CURSOR_PAIR, COLOR_BLACK, COLOR_WHITE);
+        init_pair(INACTIVE_CURSOR_PAIR, COLOR_BLACK, COLOR_CYAN);
+        init_pair(SELECTION_PAIR, COLOR_BLACK, COLOR_MAGENTA);
+        init_pair(MATCH_PAIR, COLOR_BLACK, COLOR_YELLOW);
+        init_pair(WARNING_PAIR, COLOR_RED, COLOR_BLACK);
+    }
+
+    set_ascii_lines(!utf8_locale());
 }
@@ -14,10 +14,61 @@ pub enum VAlign {
     Bottom,
 }
 
+pub struct Span {
+    pub text: String,
+    pub attrs: attr_t,
+}
+
+pub struct Line {
+    pub spans: Vec<Span>,
+    /// Indentation of the wrapped continuation rows of the line
+    pub indent: usize,
+}
+
+/// How far the markup that doesn't fit into the rectangle is scrolled
+#[derive(Clone, Copy, Default)]
+pub struct TextScroll {
+    offset: usize,
+    rows: usize,
+    height: usize,
+}
+
+impl TextScroll {
+    /// The amount of the wrapped rows scrolled out above the rectangle
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn max_offset(&self) -> usize {
+        self.rows.saturating_sub(self.height)
+    }
+}
+
 pub struct Text {
     pub text: String,
     pub halign: HAlign,
     pub valign: VAlign,
+    pub markup: Option<Vec<Line>>,
+    /// Marks the text that didn't fit into the rectangle. An empty string
+    /// cuts the text without any mark. The text and the mark are measured
+    /// in the terminal cells, so the wide characters count twice.
+    pub ellipsis: String,
+    /// Fills the whole rectangle with `style::REGULAR_PAIR` before drawing
+    /// the text, hiding whatever was drawn under it
+    pub opaque: bool,
+    /// Tabs are expanded with spaces up to the next multiple of it counted
+    /// from the start of the line, before the text is wrapped or truncated
+    pub tab_width: usize,
+    /// The markup that doesn't fit is scrolled with Up/Down, PageUp/PageDown
+    /// and Home/End, which lets it be read in full inside a `Dialog` clamped
+    /// to the screen
+    pub scroll: TextScroll,
+}
+
+impl Default for Text {
+    fn default() -> Self {
+        Self::new("")
+    }
 }
 
 impl Text {
@@ -26,21 +77,402 @@ impl Text {
             text: text.to_string(),
             halign: HAlign::Left,
             valign: VAlign::Top,
+            markup: None,
+            ellipsis: "…".to_string(),
+            opaque: false,
+            tab_width: 8,
+            scroll: TextScroll::default(),
         }
     }
 
     pub fn wrap(text: &str) -> Box<Self> {
         Box::new(Self::new(text))
     }
+
+    pub fn ellipsis(mut self, ellipsis: &str) -> Self {
+        self.ellipsis = ellipsis.to_string();
+        self
+    }
+
+    pub fn opaque(mut self, opaque: bool) -> Self {
+        self.opaque = opaque;
+        self
+    }
+
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Supports `**bold**`, `*italic*` (rendered underlined), `# headings`
+    /// and `-`/`*`/`+` bullet lists. Everything else is kept literally.
+    pub fn markdown(source: &str) -> Self {
+        Self {
+            markup: Some(source.lines().map(parse_markdown_line).collect()),
+            ..Self::new(source)
+        }
+    }
+}
+
+fn parse_markdown_line(line: &str) -> Line {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    if hashes > 0 && line[hashes..].starts_with(' ') {
+        let mut spans = parse_markdown_inline(line[hashes..].trim_start());
+        for span in spans.iter_mut() {
+            span.attrs |= A_BOLD() | A_UNDERLINE();
+        }
+        return Line { spans, indent: 0 };
+    }
+
+    let trimmed = line.trim_start();
+    for bullet in &["- ", "* ", "+ "] {
+        if let Some(item) = trimmed.strip_prefix(bullet) {
+            let indent = line.len() - trimmed.len() + 2;
+            let mut spans = vec![Span {
+                text: format!("{}• ", " ".repeat(indent - 2)),
+                attrs: A_NORMAL(),
+            }];
+            spans.extend(parse_markdown_inline(item));
+            return Line { spans, indent };
+        }
+    }
+
+    Line {
+        spans: parse_markdown_inline(line),
+        indent: 0,
+    }
+}
+
+fn parse_markdown_inline(s: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = s;
+
+    while let Some(c) = rest.chars().next() {
+        let emphasis = if let Some(inner) = rest.strip_prefix("**") {
+            inner
+                .find("**")
+                .filter(|end| *end > 0)
+                .map(|end| (&inner[..end], &inner[end + 2..], A_BOLD()))
+        } else if let Some(inner) = rest.strip_prefix('*') {
+            inner
+                .find('*')
+                .filter(|end| *end > 0)
+                .map(|end| (&inner[..end], &inner[end + 1..], A_UNDERLINE()))
+        } else {
+            None
+        };
+
+        match emphasis {
+            Some((text, tail, attrs)) => {
+                if !plain.is_empty() {
+                    spans.push(Span {
+                        text: std::mem::take(&mut plain),
+                        attrs: A_NORMAL(),
+                    });
+                }
+                spans.push(Span {
+                    text: text.to_string(),
+                    attrs,
+                });
+                rest = tail;
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    if !plain.is_empty() {
+        spans.push(Span {
+            text: plain,
+            attrs: A_NORMAL(),
+        });
+    }
+
+    spans
+}
+
+fn expand_tabs(s: &str, col: &mut usize, tab_width: usize) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        if c == '\t' {
+            let n = match tab_width {
+                0 => 0,
+                _ => tab_width - *col % tab_width,
+            };
+            result.push_str(&" ".repeat(n));
+            *col += n;
+        } else {
+            result.push(c);
+            *col += char_width(c);
+        }
+    }
+    result
+}
+
+fn expand_line_tabs(line: &Line, tab_width: usize) -> Line {
+    let mut col = 0;
+    Line {
+        spans: line
+            .spans
+            .iter()
+            .map(|span| Span {
+                text: expand_tabs(&span.text, &mut col, tab_width),
+                attrs: span.attrs,
+            })
+            .collect(),
+        indent: line.indent,
+    }
+}
+
+/// Greedily word wraps the lines into rows of at most `width` characters
+fn wrap_lines(lines: &[Line], width: usize) -> Vec<Vec<(String, attr_t)>> {
+    let mut rows = Vec::new();
+    if width == 0 {
+        return rows;
+    }
+
+    for line in lines {
+        let mut row: Vec<(String, attr_t)> = Vec::new();
+        let mut col = 0;
+
+        for span in line.spans.iter() {
+            let mut words = Vec::new();
+            let mut word = String::new();
+            for c in span.text.chars() {
+                if !word.is_empty() && c.is_whitespace() != word.ends_with(char::is_whitespace) {
+                    words.push(std::mem::take(&mut word));
+                }
+                word.push(c);
+            }
+            if !word.is_empty() {
+                words.push(word);
+            }
+
+            for word in words {
+                let len = str_width(&word);
+                if col + len <= width {
+                    row.push((word, span.attrs));
+                    col += len;
+                } else if word.starts_with(char::is_whitespace) {
+                    col = break_row(&mut rows, &mut row, line.indent, width);
+                } else {
+                    let mut chars = word.chars().peekable();
+                    if len <= width.saturating_sub(line.indent) && col > line.indent {
+                        col = break_row(&mut rows, &mut row, line.indent, width);
+                    }
+                    let mut fresh = col == 0;
+                    while chars.peek().is_some() {
+                        let mut piece = take_width(&mut chars, width.saturating_sub(col));
+                        if piece.is_empty() {
+                            if !fresh {
+                                col = break_row(&mut rows, &mut row, line.indent, width);
+                                fresh = true;
+                                continue;
+                            }
+                            // A character wider than the whole row
+                            piece.extend(chars.next());
+                        }
+                        col += str_width(&piece);
+                        row.push((piece, span.attrs));
+                        fresh = false;
+                    }
+                }
+            }
+        }
+
+        push_row(&mut rows, &mut row);
+    }
+
+    rows
+}
+
+/// Starts a continuation row indented by `indent` if it leaves any space,
+/// returning the column the row continues at. A row that has nothing but
+/// the indentation is reused instead of being pushed as a blank one.
+fn break_row(
+    rows: &mut Vec<Vec<(String, attr_t)>>,
+    row: &mut Vec<(String, attr_t)>,
+    indent: usize,
+    width: usize,
+) -> usize {
+    if row.iter().any(|(s, _)| !s.chars().all(char::is_whitespace)) {
+        push_row(rows, row);
+    } else {
+        row.clear();
+    }
+    if indent < width {
+        row.push((" ".repeat(indent), A_NORMAL()));
+        indent
+    } else {
+        0
+    }
+}
+
+fn push_row(rows: &mut Vec<Vec<(String, attr_t)>>, row: &mut Vec<(String, attr_t)>) {
+    while let Some((s, _)) = row.last() {
+        if !s.chars().all(char::is_whitespace) {
+            break;
+        }
+        row.pop();
+    }
+    rows.push(std::mem::take(row));
+}
+
+/// The amount of the terminal cells the character takes: 2 for the wide
+/// East Asian characters and most of the emoji, 0 for the combining marks
+/// and 1 for the rest
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+fn str_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Takes the characters while they fit into `width` cells
+fn take_width(chars: &mut std::iter::Peekable<std::str::Chars>, width: usize) -> String {
+    let mut result = String::new();
+    let mut taken = 0;
+    while let Some(c) = chars.peek() {
+        let w = char_width(*c);
+        if taken + w > width {
+            break;
+        }
+        taken += w;
+        result.push(*c);
+        chars.next();
+    }
+    result
+}
+
+fn truncate(text: &str, width: usize, ellipsis: &str) -> String {
+    if str_width(text) <= width {
+        return text.to_string();
+    }
+
+    let ellipsis_width = str_width(ellipsis);
+    if ellipsis_width > width {
+        return take_width(&mut text.chars().peekable(), width);
+    }
+    take_width(&mut text.chars().peekable(), width - ellipsis_width) + ellipsis
+}
+
+fn append_ellipsis(row: &mut Vec<(String, attr_t)>, width: usize, ellipsis: &str) {
+    let ellipsis_width = str_width(ellipsis);
+    if ellipsis_width == 0 || ellipsis_width > width {
+        return;
+    }
+
+    let mut budget = width - ellipsis_width;
+    for (s, _) in row.iter_mut() {
+        let n = str_width(s);
+        if n <= budget {
+            budget -= n;
+        } else {
+            *s = take_width(&mut s.chars().peekable(), budget);
+            budget = 0;
+        }
+    }
+    row.push((ellipsis.to_string(), A_NORMAL()));
+}
+
+impl Text {
+    /// Returns the scroll clamped to the rows the markup was wrapped into
+    fn render_markup(&self, context: &mut Rcui, lines: &[Line], rect: &Rect) -> TextScroll {
+        let w = rect.w.floor() as usize;
+        let h = rect.h.floor() as usize;
+        if w == 0 || h == 0 {
+            return self.scroll;
+        }
+
+        let lines: Vec<Line> = lines
+            .iter()
+            .map(|line| expand_line_tabs(line, self.tab_width))
+            .collect();
+        let mut rows = wrap_lines(&lines, w);
+        let mut scroll = TextScroll {
+            rows: rows.len(),
+            height: h,
+            ..self.scroll
+        };
+        scroll.offset = scroll.offset.min(scroll.max_offset());
+        rows.drain(..scroll.offset);
+        if rows.len() > h {
+            rows.truncate(h);
+            if let Some(last) = rows.last_mut() {
+                append_ellipsis(last, w, &self.ellipsis);
+            }
+        }
+        let n = rows.len();
+        let free_vspace = rect.h - n as f32;
+        let y = match self.valign {
+            VAlign::Top => rect.y,
+            VAlign::Centre => (rect.y + free_vspace * 0.5).floor(),
+            VAlign::Bottom => (rect.y + free_vspace).floor(),
+        } as i32;
+
+        for (i, row) in rows.iter().take(n).enumerate() {
+            let len: usize = row.iter().map(|(s, _)| str_width(s)).sum();
+            let free_hspace = rect.w - len as f32;
+            let x = match self.halign {
+                HAlign::Left => rect.x,
+                HAlign::Centre => (rect.x + free_hspace * 0.5).floor(),
+                HAlign::Right => (rect.x + free_hspace).floor(),
+            } as i32;
+
+            mv(y + i as i32, x);
+            for (s, attrs) in row.iter() {
+                context.with_style(0, *attrs, |_| addstr(s));
+            }
+        }
+        scroll
+    }
 }
 
 impl Widget for Text {
-    fn render(&mut self, _context: &mut Rcui, rect: &Rect, _active: bool) {
-        let s = self
-            .text
-            .get(..rect.w.floor() as usize)
-            .unwrap_or(&self.text);
-        let n = s.len();
+    fn render(&mut self, context: &mut Rcui, rect: &Rect, _active: bool) {
+        if rect.is_empty() {
+            return;
+        }
+
+        if self.opaque {
+            clear_rect(context, rect);
+        }
+
+        if let Some(lines) = &self.markup {
+            self.scroll = self.render_markup(context, lines, rect);
+            return;
+        }
+
+        let text = expand_tabs(&self.text, &mut 0, self.tab_width);
+        let s = truncate(&text, rect.w.floor() as usize, &self.ellipsis);
+        let n = str_width(&s);
         let free_hspace = rect.w - n as f32;
         // TODO(#3): Text does not support wrapping around
         let free_vspace = rect.h - 1.0;
@@ -58,6 +490,185 @@ impl Widget for Text {
         } as i32;
 
         mv(y, x);
-        addstr(s);
+        addstr(&s);
+    }
+
+    fn handle_event(&mut self, _context: &mut Rcui, event: &Event) -> bool {
+        let scroll = &mut self.scroll;
+        if self.markup.is_none() || scroll.max_offset() == 0 {
+            return false;
+        }
+        match event.key() {
+            Some(Key::Arrow(Dir::Up)) => scroll.offset = scroll.offset.saturating_sub(1),
+            Some(Key::Arrow(Dir::Down)) => scroll.offset += 1,
+            Some(Key::PageUp) => scroll.offset = scroll.offset.saturating_sub(scroll.height),
+            Some(Key::PageDown) => scroll.offset += scroll.height,
+            Some(Key::Home) => scroll.offset = 0,
+            Some(Key::End) => scroll.offset = scroll.max_offset(),
+            _ => return false,
+        }
+        scroll.offset = scroll.offset.min(scroll.max_offset());
+        true
+    }
+
+    fn measure(&self, available: &Rect) -> (f32, f32) {
+        match &self.markup {
+            Some(lines) => {
+                let lines: Vec<Line> = lines
+                    .iter()
+                    .map(|line| expand_line_tabs(line, self.tab_width))
+                    .collect();
+                let rows = wrap_lines(&lines, available.w.floor() as usize);
+                let w = rows
+                    .iter()
+                    .map(|row| row.iter().map(|(s, _)| str_width(s)).sum::<usize>())
+                    .max()
+                    .unwrap_or(0);
+                (w as f32, rows.len() as f32)
+            }
+            None => {
+                let text = expand_tabs(&self.text, &mut 0, self.tab_width);
+                (str_width(&text) as f32, 1.0)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(source: &str, width: usize) -> Vec<String> {
+        let lines: Vec<Line> = source.lines().map(parse_markdown_line).collect();
+        wrap_lines(&lines, width)
+            .iter()
+            .map(|row| row.iter().map(|(s, _)| s.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn parse_heading() {
+        let line = parse_markdown_line("## Title **bold**");
+        assert_eq!(line.indent, 0);
+        assert_eq!(line.spans[0].text, "Title ");
+        assert_eq!(line.spans[1].text, "bold");
+        assert_eq!(line.spans[1].attrs, A_BOLD() | A_UNDERLINE());
+    }
+
+    #[test]
+    fn parse_bullet() {
+        let line = parse_markdown_line("  * item *em*");
+        assert_eq!(line.indent, 4);
+        assert_eq!(line.spans[0].text, "  • ");
+        assert_eq!(line.spans[1].text, "item ");
+        assert_eq!(line.spans[2].text, "em");
+        assert_eq!(line.spans[2].attrs, A_UNDERLINE());
+    }
+
+    #[test]
+    fn parse_literal() {
+        let line = parse_markdown_line("#not a heading, 2 * 3");
+        assert_eq!(line.indent, 0);
+        assert_eq!(line.spans.len(), 1);
+        assert_eq!(line.spans[0].text, "#not a heading, 2 * 3");
+    }
+
+    #[test]
+    fn wrap_words() {
+        assert_eq!(rows("one two three", 8), ["one two", "three"]);
+        assert_eq!(rows("one\n\ntwo", 8), ["one", "", "two"]);
+        assert!(rows("one", 0).is_empty());
+    }
+
+    #[test]
+    fn wrap_bullet_with_hanging_indent() {
+        assert_eq!(rows("- one two three", 9), ["• one two", "  three"]);
+    }
+
+    #[test]
+    fn wrap_split_word_keeps_indent() {
+        assert_eq!(
+            rows("    - deep nested bullet here", 10),
+            [
+                "    • deep",
+                "      nest",
+                "      ed b",
+                "      ulle",
+                "      t",
+                "      here",
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_indent_wider_than_row() {
+        assert_eq!(rows("    - abcdef", 6), ["    •", "abcdef"]);
+        assert_eq!(rows("    - ab cd", 6), ["    •", "ab cd"]);
+    }
+
+    #[test]
+    fn markup_scrolls_only_when_it_overflows() {
+        let mut context = Rcui::new();
+        let mut text = Text::markdown("one\ntwo\nthree\nfour\nfive");
+        let down = Event::KeyStroke(KEY_DOWN);
+        assert!(!text.handle_event(&mut context, &down));
+
+        text.scroll = TextScroll {
+            offset: 0,
+            rows: 5,
+            height: 2,
+        };
+        assert!(text.handle_event(&mut context, &down));
+        assert_eq!(text.scroll.offset(), 1);
+        assert!(text.handle_event(&mut context, &Event::KeyStroke(KEY_NPAGE)));
+        assert!(text.handle_event(&mut context, &Event::KeyStroke(KEY_NPAGE)));
+        assert_eq!(text.scroll.offset(), 3);
+        assert!(text.handle_event(&mut context, &Event::KeyStroke(KEY_HOME)));
+        assert_eq!(text.scroll.offset(), 0);
+    }
+
+    #[test]
+    fn truncate_by_display_width() {
+        assert_eq!(truncate("hello", 5, "…"), "hello");
+        assert_eq!(truncate("hello world", 8, "…"), "hello w…");
+        assert_eq!(truncate("hello world", 8, "..."), "hello...");
+        assert_eq!(truncate("hello", 2, "..."), "he");
+        assert_eq!(truncate("日本語です", 7, "…"), "日本語…");
+        assert_eq!(truncate("日本語です", 6, "…"), "日本…");
+        assert_eq!(truncate("abc", 2, "語"), "語");
+        assert_eq!(truncate("abc", 1, "語"), "a");
+    }
+
+    #[test]
+    fn append_ellipsis_by_display_width() {
+        let mut row = vec![
+            ("日本".to_string(), A_NORMAL()),
+            ("abc".to_string(), A_BOLD()),
+        ];
+        append_ellipsis(&mut row, 6, "…");
+        let row: Vec<&str> = row.iter().map(|(s, _)| s.as_str()).collect();
+        assert_eq!(row, ["日本", "a", "…"]);
+
+        let mut row = vec![("abc".to_string(), A_NORMAL())];
+        append_ellipsis(&mut row, 2, "...");
+        assert_eq!(row[0].0, "abc");
+        assert_eq!(row.len(), 1);
+    }
+
+    #[test]
+    fn expand_tabs_to_stops() {
+        let mut col = 0;
+        assert_eq!(expand_tabs("a\tb", &mut col, 4), "a   b");
+        assert_eq!(col, 5);
+        assert_eq!(expand_tabs("\tc", &mut col, 4), "   c");
+        assert_eq!(col, 9);
+        assert_eq!(expand_tabs("\td", &mut 0, 0), "d");
+        assert_eq!(expand_tabs("語\tx", &mut 0, 4), "語  x");
+    }
+
+    #[test]
+    fn wrap_wide_characters() {
+        assert_eq!(rows("日本語です", 4), ["日本", "語で", "す"]);
+        assert_eq!(rows("語", 1), ["語"]);
     }
 }